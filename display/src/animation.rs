@@ -0,0 +1,335 @@
+//! Effects drawn one frame at a time, so the game loop keeps reading input while they play and
+//! can cut them short.
+//!
+//! Each call to [`Animation::advance`] draws the next frame, which should then be left on the
+//! display for [`Animation::frame_ms`]:
+//!
+//! ```ignore
+//! let mut animation = Animation::from(ScrollText::new("LOSE", DEFAULT_COLUMN_MS).unwrap());
+//! while animation.advance(&mut display) {
+//!     delay.delay_ms(animation.frame_ms());
+//! }
+//! ```
+
+use crate::{connector::Connector, matrix_display::MAX7219, text::Text};
+
+/// The most bytes of text a [`ScrollText`] holds.
+pub const TEXT_CAPACITY: usize = 24;
+
+#[derive(Debug, Clone)]
+pub enum Animation {
+    Scroll(ScrollText),
+    Flash(Flash),
+    Wipe(Wipe),
+}
+
+impl Animation {
+    /// How long each frame should stay on the display.
+    pub fn frame_ms(&self) -> u16 {
+        match self {
+            Self::Scroll(scroll) => scroll.column_ms,
+            Self::Flash(flash) => flash.period_ms,
+            Self::Wipe(wipe) => wipe.column_ms,
+        }
+    }
+
+    /// Draws the next frame, `false` once the animation has finished and nothing was drawn.
+    pub fn advance<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        display: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> bool
+    where
+        CONNECTOR: Connector,
+    {
+        match self {
+            Self::Scroll(scroll) => scroll.advance(display),
+            Self::Flash(flash) => flash.advance(display),
+            Self::Wipe(wipe) => wipe.advance(display),
+        }
+    }
+}
+
+impl From<ScrollText> for Animation {
+    fn from(scroll: ScrollText) -> Self {
+        Self::Scroll(scroll)
+    }
+}
+
+impl From<Flash> for Animation {
+    fn from(flash: Flash) -> Self {
+        Self::Flash(flash)
+    }
+}
+
+impl From<Wipe> for Animation {
+    fn from(wipe: Wipe) -> Self {
+        Self::Wipe(wipe)
+    }
+}
+
+/// Text scrolled in from the right across every device of the chain until it has left on the
+/// left, a column per frame.
+#[derive(Debug, Clone)]
+pub struct ScrollText {
+    text: [u8; TEXT_CAPACITY],
+    len: usize,
+    column_ms: u16,
+    // Frames drawn so far
+    offset: usize,
+}
+
+impl ScrollText {
+    /// `None` if `text` is longer than `TEXT_CAPACITY` bytes.
+    pub fn new(text: &str, column_ms: u16) -> Option<Self> {
+        let mut scroll = Self {
+            text: [0; TEXT_CAPACITY],
+            len: 0,
+            column_ms,
+            offset: 0,
+        };
+        scroll.push_str(text).then_some(scroll)
+    }
+
+    /// Adds `text` to the end, returns `false` and leaves the text as it was if it doesn't fit.
+    pub fn push_str(&mut self, text: &str) -> bool {
+        let end = self.len + text.len();
+        if end > TEXT_CAPACITY {
+            return false;
+        }
+        self.text[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        true
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever whole strings are copied in
+        core::str::from_utf8(&self.text[..self.len]).unwrap_or_default()
+    }
+
+    fn advance<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        display: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> bool
+    where
+        CONNECTOR: Connector,
+    {
+        let text = Text::new(self.as_str());
+        let width = 8 * DEVICES;
+        if self.offset >= width + text.width() {
+            return false;
+        }
+        let offset = self.offset + 1;
+        for x in 0..width {
+            // The text starts just past the right edge
+            let row = (x + offset)
+                .checked_sub(width)
+                .map_or(0, |i| text.column(i));
+            display.set_row(x as u8, row);
+        }
+        display.flush();
+        self.offset = offset;
+        true
+    }
+}
+
+/// Every LED turned on and off again, `times` over.
+#[derive(Debug, Clone)]
+pub struct Flash {
+    times: u8,
+    // How long the LEDs stay on, and then off
+    period_ms: u16,
+    frame: u8,
+}
+
+impl Flash {
+    pub fn new(times: u8, period_ms: u16) -> Self {
+        Self {
+            times,
+            period_ms,
+            frame: 0,
+        }
+    }
+
+    fn advance<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        display: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> bool
+    where
+        CONNECTOR: Connector,
+    {
+        if self.frame >= self.times.saturating_mul(2) {
+            return false;
+        }
+        display.flash(self.frame % 2 == 0);
+        self.frame += 1;
+        true
+    }
+}
+
+/// The display filled column by column from the left, then emptied the same way.
+#[derive(Debug, Clone)]
+pub struct Wipe {
+    column_ms: u16,
+    frame: usize,
+}
+
+impl Wipe {
+    pub fn new(column_ms: u16) -> Self {
+        Self {
+            column_ms,
+            frame: 0,
+        }
+    }
+
+    fn advance<CONNECTOR, const DEVICES: usize>(
+        &mut self,
+        display: &mut MAX7219<CONNECTOR, DEVICES>,
+    ) -> bool
+    where
+        CONNECTOR: Connector,
+    {
+        let width = 8 * DEVICES;
+        if self.frame >= 2 * width {
+            return false;
+        }
+        let (x, row) = if self.frame < width {
+            (self.frame, 0xFF)
+        } else {
+            (self.frame - width, 0x00)
+        };
+        display.set_row(x as u8, row);
+        display.flush();
+        self.frame += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Animation, Flash, ScrollText, Wipe, TEXT_CAPACITY};
+    use crate::{
+        connector::Connector,
+        matrix_display::MAX7219,
+        mock::MockMatrix,
+        text::{Number, Text},
+    };
+
+    /// What each device shows after every frame, until the animation finishes.
+    fn play<CONNECTOR, const DEVICES: usize>(
+        matrix: &MockMatrix<DEVICES>,
+        display: &mut MAX7219<CONNECTOR, DEVICES>,
+        mut animation: Animation,
+    ) -> Vec<[[u8; 8]; DEVICES]>
+    where
+        CONNECTOR: Connector,
+    {
+        let mut frames = Vec::new();
+        while animation.advance(display) {
+            frames.push(core::array::from_fn(|d| matrix.device_registers(d).digits));
+        }
+        // And stays finished
+        assert!(!animation.advance(display));
+        frames
+    }
+
+    #[test]
+    fn scroll_text() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+        let scroll = ScrollText::new("OK", 50).unwrap();
+        assert_eq!(Animation::from(scroll.clone()).frame_ms(), 50);
+        let frames = play(&matrix, &mut display, scroll.into());
+
+        let text = Text::new("OK");
+        // In from the right, off to the left
+        assert_eq!(frames.len(), 8 + 11);
+        // Row x is shown on digit 7 - x
+        assert_eq!(frames[0][0], [text.column(0), 0, 0, 0, 0, 0, 0, 0]);
+        let mut all_in = [0; 8];
+        for x in 0..8 {
+            all_in[7 - x] = text.column(x);
+        }
+        assert_eq!(frames[7][0], all_in);
+        assert_eq!(frames[18][0], [0; 8]);
+        assert_eq!(matrix.digits(), [0; 8]);
+    }
+
+    #[test]
+    fn chained_scroll() {
+        let matrix = MockMatrix::<2>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 2>::new_chained(data, cs, clk);
+        let mut scroll = ScrollText::new("SCORE ", 10).unwrap();
+        assert!(scroll.push_str(Number::new(42).as_str()));
+        assert_eq!(scroll.as_str(), "SCORE 42");
+        let frames = play(&matrix, &mut display, scroll.into());
+
+        let text = Text::new("SCORE 42");
+        assert_eq!(frames.len(), 16 + text.width());
+        // Starting on the first column of the first device
+        let frame = frames[15];
+        for x in 0..16 {
+            assert_eq!(frame[x / 8][7 - x % 8], text.column(x), "{x}");
+        }
+
+        // Each frame sends every digit at most once, to the whole chain at a time
+        let mut scroll = Animation::from(ScrollText::new("SCORE", 10).unwrap());
+        for _ in 0..16 {
+            matrix.clear_frames();
+            assert!(scroll.advance(&mut display));
+            assert!(matrix.frames().len() <= 8 * 2);
+        }
+
+        let empty = ScrollText::new("", 10).unwrap();
+        let frames = play(&matrix, &mut display, empty.into());
+        assert_eq!(frames.len(), 16);
+    }
+
+    #[test]
+    fn text_capacity() {
+        assert!(ScrollText::new("A message far too long to fit", 10).is_none());
+        let full = "A".repeat(TEXT_CAPACITY);
+        let mut scroll = ScrollText::new(&full, 10).unwrap();
+        assert_eq!(scroll.as_str(), full);
+        assert!(!scroll.push_str("!"));
+        assert_eq!(scroll.as_str(), full);
+        // Counted in bytes, not characters
+        let accents = "\u{e9}".repeat(TEXT_CAPACITY / 2);
+        assert!(ScrollText::new(&accents, 10).is_some());
+        assert!(ScrollText::new(&format!("a{accents}"), 10).is_none());
+    }
+
+    #[test]
+    fn flash() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+        let flash = Flash::new(2, 500);
+        assert_eq!(Animation::from(flash.clone()).frame_ms(), 500);
+        let frames = play(&matrix, &mut display, flash.into());
+        assert_eq!(frames, [[[0xFF; 8]], [[0; 8]], [[0xFF; 8]], [[0; 8]]]);
+
+        assert!(play(&matrix, &mut display, Flash::new(0, 500).into()).is_empty());
+    }
+
+    #[test]
+    fn wipe() {
+        let matrix = MockMatrix::<2>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 2>::new_chained(data, cs, clk);
+        let frames = play(&matrix, &mut display, Wipe::new(20).into());
+        assert_eq!(frames.len(), 32);
+
+        let lit = |frame: &[[u8; 8]; 2]| {
+            (0..16)
+                .filter(|&x| frame[x / 8][7 - x % 8] == 0xFF)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(lit(&frames[0]), [0]);
+        assert_eq!(lit(&frames[9]), (0..10).collect::<Vec<_>>());
+        assert_eq!(lit(&frames[15]), (0..16).collect::<Vec<_>>());
+        assert_eq!(lit(&frames[20]), (5..16).collect::<Vec<_>>());
+        assert!(lit(&frames[31]).is_empty());
+    }
+}
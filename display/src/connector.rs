@@ -0,0 +1,88 @@
+//! The ways a chain of MAX7219s can be driven, see [`MAX7219`](crate::matrix_display::MAX7219).
+
+use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+
+/// Sends 16 bit frames to a chain of MAX7219s.
+pub trait Connector {
+    /// Latches one frame into every device of the chain, `frames[0]` ends up in device 0.
+    fn send(&mut self, frames: &[u16]);
+}
+
+/// Bit-bangs the frames through three GPIO pins.
+pub struct PinConnector<DATA, CS, CLK> {
+    data: DATA,
+    cs: CS,
+    clk: CLK,
+}
+
+impl<DATA, CS, CLK> PinConnector<DATA, CS, CLK>
+where
+    DATA: OutputPin,
+    CS: OutputPin,
+    CLK: OutputPin,
+{
+    pub fn new(data: DATA, cs: CS, clk: CLK) -> Self {
+        Self { data, cs, clk }
+    }
+
+    fn shift_out(&mut self, value: u16) {
+        for i in 0..16 {
+            if value & (1 << (15 - i)) > 0 {
+                _ = self.data.set_high();
+            } else {
+                _ = self.data.set_low();
+            }
+
+            _ = self.clk.set_high();
+            _ = self.clk.set_low();
+        }
+    }
+}
+
+impl<DATA, CS, CLK> Connector for PinConnector<DATA, CS, CLK>
+where
+    DATA: OutputPin,
+    CS: OutputPin,
+    CLK: OutputPin,
+{
+    fn send(&mut self, frames: &[u16]) {
+        _ = self.cs.set_low();
+        // The first frame shifted out is pushed furthest down the chain
+        for frame in frames.iter().rev() {
+            self.shift_out(*frame);
+        }
+        _ = self.cs.set_high();
+    }
+}
+
+/// Uses a hardware SPI peripheral, with the chip select (LOAD) line on a separate pin.
+///
+/// The SPI has to be configured for mode 0, most significant bit first and at most 10 MHz.
+pub struct SpiConnector<SPI, CS> {
+    spi: SPI,
+    cs: CS,
+}
+
+impl<SPI, CS> SpiConnector<SPI, CS>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Self { spi, cs }
+    }
+}
+
+impl<SPI, CS> Connector for SpiConnector<SPI, CS>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    fn send(&mut self, frames: &[u16]) {
+        _ = self.cs.set_low();
+        for frame in frames.iter().rev() {
+            _ = self.spi.write(&frame.to_be_bytes());
+        }
+        _ = self.cs.set_high();
+    }
+}
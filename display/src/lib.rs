@@ -0,0 +1,8 @@
+#![cfg_attr(not(any(test, feature = "mock")), no_std)]
+
+pub mod animation;
+pub mod connector;
+pub mod matrix_display;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod text;
@@ -0,0 +1,565 @@
+//! TAKEN FROM https://github.com/maikelwever/max7219/blob/master/src/lib.rs
+//! Modified to be compatible with newer version of embedded-hal and to be customized to this
+//! crate.
+//!
+//! ORIGINAL:
+//!
+//! A platform agnostic driver to interface with the MAX7219 (LED matrix display driver)
+//!
+//! This driver was built using [`embedded-hal`] traits.
+//!
+//! [`embedded-hal`]: https://docs.rs/embedded-hal/~0.1
+
+#![deny(unsafe_code)]
+//#![deny(warnings)]
+
+use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+
+use crate::connector::{Connector, PinConnector, SpiConnector};
+
+pub enum Command {
+    Noop = 0x00,
+    Digit0 = 0x01,
+    Digit1 = 0x02,
+    Digit2 = 0x03,
+    Digit3 = 0x04,
+    Digit4 = 0x05,
+    Digit5 = 0x06,
+    Digit6 = 0x07,
+    Digit7 = 0x08,
+    DecodeMode = 0x09,
+    Intensity = 0x0A,
+    ScanLimit = 0x0B,
+    Power = 0x0C,
+    DisplayTest = 0x0F,
+}
+
+pub enum DecodeMode {
+    NoDecode = 0x00,
+    CodeBDigit0 = 0x01,
+    CodeBDigits3_0 = 0x0F,
+    CodeBDigits7_0 = 0xFF,
+}
+
+/// `DEVICES` MAX7219s cascaded on the same data, chip select and clock lines (DOUT of each chip
+/// connected to DIN of the next). Device 0 is the one connected to the microcontroller.
+///
+/// The chain is driven through a [`Connector`], either bit-banged over three pins
+/// ([`new`](MAX7219::new)) or with a hardware SPI ([`from_spi`](MAX7219::from_spi)).
+///
+/// The devices are laid out side by side along x, so `x / 8` selects the device.
+///
+/// Besides the `write_*` methods, which are sent straight away, a whole frame can be drawn
+/// off-screen with [`set_pixel`](Self::set_pixel) and sent at once with [`flush`](Self::flush).
+pub struct MAX7219<CONNECTOR, const DEVICES: usize = 1> {
+    connector: CONNECTOR,
+    // What the devices currently show
+    buffer: [[u8; 8]; DEVICES],
+    // The off-screen frame sent by `flush`
+    framebuffer: [[u8; 8]; DEVICES],
+}
+
+impl<DATA, CS, CLK> MAX7219<PinConnector<DATA, CS, CLK>>
+where
+    DATA: OutputPin,
+    CS: OutputPin,
+    CLK: OutputPin,
+{
+    pub fn new(data: DATA, cs: CS, clk: CLK) -> Self {
+        Self::new_chained(data, cs, clk)
+    }
+}
+
+impl<DATA, CS, CLK, const DEVICES: usize> MAX7219<PinConnector<DATA, CS, CLK>, DEVICES>
+where
+    DATA: OutputPin,
+    CS: OutputPin,
+    CLK: OutputPin,
+{
+    pub fn new_chained(data: DATA, cs: CS, clk: CLK) -> Self {
+        Self::with_connector(PinConnector::new(data, cs, clk))
+    }
+}
+
+impl<SPI, CS> MAX7219<SpiConnector<SPI, CS>>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    pub fn from_spi(spi: SPI, cs: CS) -> Self {
+        Self::from_spi_chained(spi, cs)
+    }
+}
+
+impl<SPI, CS, const DEVICES: usize> MAX7219<SpiConnector<SPI, CS>, DEVICES>
+where
+    SPI: Write<u8>,
+    CS: OutputPin,
+{
+    pub fn from_spi_chained(spi: SPI, cs: CS) -> Self {
+        Self::with_connector(SpiConnector::new(spi, cs))
+    }
+}
+
+impl<CONNECTOR, const DEVICES: usize> MAX7219<CONNECTOR, DEVICES>
+where
+    CONNECTOR: Connector,
+{
+    const NOOP: u16 = Self::frame(Command::Noop as u8, 0x00);
+
+    pub fn with_connector(connector: CONNECTOR) -> Self {
+        let mut max7219 = MAX7219 {
+            connector,
+            buffer: [[0; 8]; DEVICES],
+            framebuffer: [[0; 8]; DEVICES],
+        };
+
+        max7219.init();
+        max7219
+    }
+
+    pub fn init(&mut self) {
+        self.write_command(Command::DisplayTest);
+        self.write_data(Command::ScanLimit, 0x07);
+        self.set_decode_mode(DecodeMode::NoDecode);
+        self.clear_display();
+        self.power_off();
+    }
+
+    pub fn devices(&self) -> usize {
+        DEVICES
+    }
+
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.write_data(Command::DecodeMode, mode as u8);
+    }
+
+    pub fn power_on(&mut self) {
+        self.write_data(Command::Power, 0x01);
+    }
+
+    pub fn power_off(&mut self) {
+        self.write_data(Command::Power, 0x00);
+    }
+
+    pub fn write_command(&mut self, command: Command) {
+        self.write_data(command, 0x00);
+    }
+
+    pub fn write_data(&mut self, command: Command, data: u8) {
+        self.write_raw(command as u8, data);
+    }
+
+    pub fn write_device_data(&mut self, device: usize, command: Command, data: u8) {
+        self.write_device_raw(device, command as u8, data);
+    }
+
+    #[inline]
+    fn split_x(x: u8) -> (usize, u8) {
+        let x = x as usize % (8 * DEVICES);
+        (x / 8, (x % 8) as u8)
+    }
+
+    pub fn write_pos(&mut self, x: u8, y: u8, state: bool) {
+        let (device, x) = Self::split_x(x);
+        let y = y & 0x07;
+        let row = self.buffer[device][7 - x as usize];
+        let row = if state {
+            row | (1 << y)
+        } else {
+            row & !(1 << y)
+        };
+        self.write_device_raw(device, 8 - x, row);
+    }
+
+    pub fn write_row(&mut self, x: u8, row: u8) {
+        let (device, x) = Self::split_x(x);
+        self.write_device_raw(device, 8 - x, row);
+    }
+
+    /// Writes the same register of every device.
+    pub fn write_raw(&mut self, header: u8, data: u8) {
+        for device in 0..DEVICES {
+            self.save(device, header, data);
+        }
+        self.send([Self::frame(header, data); DEVICES]);
+    }
+
+    /// Writes a register of a single device, the rest of the chain is sent No-ops.
+    pub fn write_device_raw(&mut self, device: usize, header: u8, data: u8) {
+        if device >= DEVICES {
+            return;
+        }
+        self.save(device, header, data);
+
+        let mut frames = [Self::NOOP; DEVICES];
+        frames[device] = Self::frame(header, data);
+        self.send(frames);
+    }
+
+    /// Sets a pixel of the off-screen frame, nothing is sent until [`flush`](Self::flush).
+    pub fn set_pixel(&mut self, x: u8, y: u8, state: bool) {
+        let (device, x) = Self::split_x(x);
+        let y = y & 0x07;
+        let row = &mut self.framebuffer[device][7 - x as usize];
+        if state {
+            *row |= 1 << y;
+        } else {
+            *row &= !(1 << y);
+        }
+    }
+
+    /// Sets a whole row of the off-screen frame, nothing is sent until [`flush`](Self::flush).
+    pub fn set_row(&mut self, x: u8, row: u8) {
+        let (device, x) = Self::split_x(x);
+        self.framebuffer[device][7 - x as usize] = row;
+    }
+
+    /// Turns every pixel of the off-screen frame off.
+    pub fn clear_frame(&mut self) {
+        self.framebuffer = [[0; 8]; DEVICES];
+    }
+
+    /// Sends the rows of the off-screen frame which differ from what is shown. Each digit is sent
+    /// to the whole chain at once, with No-ops for the devices where that row did not change.
+    pub fn flush(&mut self) {
+        for digit in 0..8 {
+            let mut frames = [Self::NOOP; DEVICES];
+            let mut changed = false;
+            for (device, frame) in frames.iter_mut().enumerate() {
+                let row = self.framebuffer[device][digit];
+                if row != self.buffer[device][digit] {
+                    self.buffer[device][digit] = row;
+                    *frame = Self::frame(digit as u8 + 1, row);
+                    changed = true;
+                }
+            }
+            if changed {
+                self.send(frames);
+            }
+        }
+    }
+
+    #[inline]
+    fn save(&mut self, device: usize, header: u8, data: u8) {
+        // Save the "pixel state" to the internal buffer, the off-screen frame follows so the
+        // next flush does not undo the write
+        if 0 < header && header < 9 {
+            self.buffer[device][header as usize - 1] = data;
+            self.framebuffer[device][header as usize - 1] = data;
+        }
+    }
+
+    #[inline]
+    const fn frame(header: u8, data: u8) -> u16 {
+        (data as u16) | ((header as u16) << 8)
+    }
+
+    #[inline]
+    fn send(&mut self, frames: [u16; DEVICES]) {
+        self.connector.send(&frames);
+    }
+
+    pub fn set_intensity(&mut self, intensity: u8) {
+        self.write_data(Command::Intensity, intensity);
+    }
+
+    pub fn clear_display(&mut self) {
+        for i in 1..9 {
+            self.write_raw(i, 0x00);
+        }
+    }
+
+    pub fn test(&mut self, is_on: bool) {
+        if is_on {
+            self.write_raw(0x01, 0x01);
+        } else {
+            self.write_raw(0x01, 0x00);
+        }
+    }
+
+    pub fn flash(&mut self, on: bool) {
+        let state = if on {
+            0xFF
+        } else {
+            0x00
+        };
+        for i in 1..9 {
+            self.write_raw(i, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Command, MAX7219};
+    use crate::mock::{Frame, MockMatrix};
+
+    #[test]
+    fn init() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+        let registers = matrix.registers();
+        assert_eq!(registers.scan_limit, 0x07);
+        assert_eq!(registers.decode_mode, 0x00);
+        assert_eq!(registers.digits, [0; 8]);
+        assert!(registers.shutdown);
+        assert!(!registers.display_test);
+
+        display.power_on();
+        display.set_intensity(1);
+        let registers = matrix.registers();
+        assert!(!registers.shutdown);
+        assert_eq!(registers.intensity, 1);
+    }
+
+    #[test]
+    fn write_pos() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+        matrix.clear_frames();
+
+        display.write_pos(0, 0, true);
+        assert_eq!(
+            matrix.frames(),
+            [Frame {
+                register: 0x08,
+                data: 0b0000_0001
+            }]
+        );
+        display.write_pos(0, 7, true);
+        display.write_pos(7, 3, true);
+        assert!(matrix.is_lit(0, 0));
+        assert!(matrix.is_lit(0, 7));
+        assert!(matrix.is_lit(7, 3));
+        assert_eq!(matrix.digits()[7], 0b1000_0001);
+        assert_eq!(matrix.digits()[0], 0b0000_1000);
+
+        display.write_pos(0, 0, false);
+        assert!(!matrix.is_lit(0, 0));
+        assert!(matrix.is_lit(0, 7));
+        assert_eq!(matrix.frames().len(), 4);
+    }
+
+    #[test]
+    fn flash_and_clear() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+
+        display.flash(true);
+        assert_eq!(matrix.digits(), [0xFF; 8]);
+        display.flash(false);
+        assert_eq!(matrix.digits(), [0x00; 8]);
+
+        display.write_pos(3, 4, true);
+        matrix.clear_frames();
+        display.clear_display();
+        assert_eq!(matrix.digits(), [0x00; 8]);
+        let registers: [u8; 8] = core::array::from_fn(|i| matrix.frames()[i].register);
+        assert_eq!(registers, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn chained_init() {
+        let matrix = MockMatrix::<4>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 4>::new_chained(data, cs, clk);
+        display.power_on();
+        display.set_intensity(3);
+        for device in 0..4 {
+            let registers = matrix.device_registers(device);
+            assert_eq!(registers.scan_limit, 0x07);
+            assert_eq!(registers.intensity, 3);
+            assert!(!registers.shutdown);
+        }
+    }
+
+    #[test]
+    fn chained_write_pos() {
+        let matrix = MockMatrix::<4>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 4>::new_chained(data, cs, clk);
+        matrix.clear_frames();
+
+        display.write_pos(17, 5, true);
+        // Only the third device is written, the others get a No-op
+        let noop = Frame {
+            register: Command::Noop as u8,
+            data: 0,
+        };
+        assert_eq!(
+            matrix.frames(),
+            [
+                noop,
+                noop,
+                Frame {
+                    register: 0x07,
+                    data: 0b0010_0000
+                },
+                noop
+            ]
+        );
+        assert!(matrix.is_lit(17, 5));
+        assert_eq!(matrix.device_registers(2).digits[6], 0b0010_0000);
+        for device in [0, 1, 3] {
+            assert_eq!(matrix.device_registers(device).digits, [0; 8]);
+        }
+
+        for x in 0..32 {
+            display.write_pos(x, 0, true);
+        }
+        for x in 0..32 {
+            assert!(matrix.is_lit(x, 0));
+        }
+        display.clear_display();
+        for device in 0..4 {
+            assert_eq!(matrix.device_registers(device).digits, [0; 8]);
+        }
+    }
+
+    #[test]
+    fn chained_device_data() {
+        let matrix = MockMatrix::<2>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 2>::new_chained(data, cs, clk);
+        display.write_device_data(1, Command::Intensity, 0x0F);
+        assert_eq!(matrix.device_registers(0).intensity, 0);
+        assert_eq!(matrix.device_registers(1).intensity, 0x0F);
+    }
+
+    #[test]
+    fn flush_changed_rows() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+        matrix.clear_frames();
+
+        display.set_pixel(0, 0, true);
+        display.set_pixel(0, 3, true);
+        display.set_pixel(5, 1, true);
+        assert!(matrix.frames().is_empty());
+        display.flush();
+        // One frame per row touched
+        assert_eq!(matrix.frames().len(), 2);
+        assert!(matrix.is_lit(0, 0));
+        assert!(matrix.is_lit(0, 3));
+        assert!(matrix.is_lit(5, 1));
+
+        matrix.clear_frames();
+        display.flush();
+        assert!(matrix.frames().is_empty());
+
+        // Redrawing the same frame from scratch sends nothing either
+        display.clear_frame();
+        display.set_pixel(0, 0, true);
+        display.set_pixel(0, 3, true);
+        display.set_pixel(5, 1, true);
+        display.flush();
+        assert!(matrix.frames().is_empty());
+
+        display.clear_frame();
+        display.set_pixel(5, 1, true);
+        display.flush();
+        assert_eq!(
+            matrix.frames(),
+            [Frame {
+                register: 0x08,
+                data: 0x00
+            }]
+        );
+        assert!(!matrix.is_lit(0, 0));
+        assert!(matrix.is_lit(5, 1));
+    }
+
+    #[test]
+    fn flush_after_write() {
+        let matrix = MockMatrix::new();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::new(data, cs, clk);
+
+        display.flash(true);
+        matrix.clear_frames();
+        display.flush();
+        assert!(matrix.frames().is_empty());
+
+        display.clear_frame();
+        display.set_pixel(2, 2, true);
+        display.flush();
+        assert_eq!(matrix.frames().len(), 8);
+        for x in 0..8 {
+            for y in 0..8 {
+                assert_eq!(matrix.is_lit(x, y), (x, y) == (2, 2));
+            }
+        }
+    }
+
+    #[test]
+    fn chained_flush() {
+        let matrix = MockMatrix::<2>::new_chained();
+        let (data, cs, clk) = matrix.pins();
+        let mut display = MAX7219::<_, 2>::new_chained(data, cs, clk);
+        matrix.clear_frames();
+
+        display.set_pixel(1, 0, true);
+        display.set_pixel(9, 7, true);
+        display.flush();
+        // Both devices share digit 7, so a single latch updates both
+        assert_eq!(
+            matrix.frames(),
+            [
+                Frame {
+                    register: 0x07,
+                    data: 0b0000_0001
+                },
+                Frame {
+                    register: 0x07,
+                    data: 0b1000_0000
+                }
+            ]
+        );
+        assert!(matrix.is_lit(1, 0));
+        assert!(matrix.is_lit(9, 7));
+    }
+
+    #[test]
+    fn spi() {
+        let matrix = MockMatrix::new();
+        let (spi, cs) = matrix.spi();
+        let mut display = MAX7219::from_spi(spi, cs);
+        display.power_on();
+        assert!(!matrix.registers().shutdown);
+        assert_eq!(matrix.registers().scan_limit, 0x07);
+
+        let sent = matrix.spi_bytes().len();
+        display.write_pos(0, 1, true);
+        assert_eq!(&matrix.spi_bytes()[sent..], [0x08, 0b0000_0010]);
+        assert!(matrix.is_lit(0, 1));
+
+        display.clear_frame();
+        display.set_pixel(4, 4, true);
+        display.flush();
+        assert!(!matrix.is_lit(0, 1));
+        assert!(matrix.is_lit(4, 4));
+    }
+
+    #[test]
+    fn chained_spi() {
+        let matrix = MockMatrix::<3>::new_chained();
+        let (spi, cs) = matrix.spi();
+        let mut display = MAX7219::<_, 3>::from_spi_chained(spi, cs);
+
+        let sent = matrix.spi_bytes().len();
+        display.write_pos(8, 0, true);
+        // The frame of the last device is shifted out first, so device 1 sits in the middle
+        assert_eq!(
+            &matrix.spi_bytes()[sent..],
+            [0x00, 0x00, 0x08, 0b0000_0001, 0x00, 0x00]
+        );
+        assert!(matrix.is_lit(8, 0));
+        assert_eq!(matrix.device_registers(0).digits, [0; 8]);
+        assert_eq!(matrix.device_registers(2).digits, [0; 8]);
+    }
+}
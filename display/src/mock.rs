@@ -0,0 +1,299 @@
+//! A fake MAX7219 for host tests.
+//!
+//! [`MockMatrix`] hands out three [`MockPin`]s to be passed to [`MAX7219::new`]. It samples the
+//! data line on every rising clock edge while chip select is low and latches the last 16 bits
+//! as a frame on the rising edge of chip select, the same as the real chip does. The decoded
+//! register state and every frame clocked in can then be inspected.
+//!
+//! A chain of devices is emulated by [`MockMatrix::new_chained`], where the bits overflowing
+//! the shift register of a device are shifted into the next one.
+//!
+//! For [`MAX7219::from_spi`], [`MockMatrix::spi`] hands out a [`MockSpi`] and chip select pin
+//! instead. The bytes written to the SPI are recorded and clocked into the same emulated chain.
+//!
+//! [`MAX7219::new`]: crate::matrix_display::MAX7219::new
+//! [`MAX7219::from_spi`]: crate::matrix_display::MAX7219::from_spi
+
+use core::{cell::RefCell, convert::Infallible};
+use std::vec::Vec;
+
+use embedded_hal::{blocking::spi::Write, digital::v2::OutputPin};
+
+/// A 16 bit frame latched by the chip, split into its register address and data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub register: u8,
+    pub data: u8,
+}
+
+impl Frame {
+    fn from_bits(bits: u16) -> Self {
+        Self {
+            // D12-D15 are "don't care" bits
+            register: ((bits >> 8) & 0x0F) as u8,
+            data: bits as u8,
+        }
+    }
+}
+
+/// The state of the MAX7219 registers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Registers {
+    /// Digit 0 to 7, i.e. the values written to registers 0x01 to 0x08.
+    pub digits: [u8; 8],
+    pub decode_mode: u8,
+    pub intensity: u8,
+    pub scan_limit: u8,
+    pub shutdown: bool,
+    pub display_test: bool,
+}
+
+impl Default for Registers {
+    /// The state of the chip on power up: shut down with everything else cleared.
+    fn default() -> Self {
+        Self {
+            digits: [0; 8],
+            decode_mode: 0,
+            intensity: 0,
+            scan_limit: 0,
+            shutdown: true,
+            display_test: false,
+        }
+    }
+}
+
+impl Registers {
+    fn apply(&mut self, frame: Frame) {
+        match frame.register {
+            0x01..=0x08 => self.digits[frame.register as usize - 1] = frame.data,
+            0x09 => self.decode_mode = frame.data,
+            0x0A => self.intensity = frame.data & 0x0F,
+            0x0B => self.scan_limit = frame.data & 0x07,
+            0x0C => self.shutdown = frame.data & 0x01 == 0,
+            0x0F => self.display_test = frame.data & 0x01 == 1,
+            // No-op, and the registers the chip does not have
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bus<const DEVICES: usize> {
+    data: bool,
+    cs: bool,
+    clk: bool,
+    // Device 0, the first in the chain, at the front
+    shift: [u16; DEVICES],
+    bits: usize,
+    registers: [Registers; DEVICES],
+    frames: Vec<Frame>,
+    spi_bytes: Vec<u8>,
+}
+
+impl<const DEVICES: usize> Bus<DEVICES> {
+    fn new() -> Self {
+        Self {
+            data: false,
+            // Chip select idles high, nothing is clocked in until it is pulled low
+            cs: true,
+            clk: false,
+            shift: [0; DEVICES],
+            bits: 0,
+            registers: [Registers::default(); DEVICES],
+            frames: Vec::new(),
+            spi_bytes: Vec::new(),
+        }
+    }
+
+    fn set(&mut self, line: Line, high: bool) {
+        match line {
+            Line::Data => self.data = high,
+            Line::Clk => {
+                if high && !self.clk && !self.cs {
+                    self.shift_in();
+                }
+                self.clk = high;
+            }
+            Line::Cs => {
+                if high && !self.cs {
+                    self.latch();
+                }
+                self.cs = high;
+            }
+        }
+    }
+
+    fn write_spi(&mut self, byte: u8) {
+        self.spi_bytes.push(byte);
+        if self.cs {
+            return;
+        }
+        for i in (0..8).rev() {
+            self.data = byte & (1 << i) != 0;
+            self.shift_in();
+        }
+    }
+
+    fn shift_in(&mut self) {
+        let mut carry = self.data as u16;
+        for shift in self.shift.iter_mut() {
+            let out = *shift >> 15;
+            *shift = (*shift << 1) | carry;
+            carry = out;
+        }
+        self.bits += 1;
+    }
+
+    fn latch(&mut self) {
+        assert!(
+            self.bits == 16 * DEVICES,
+            "chip select released after {} bits, expected {}",
+            self.bits,
+            16 * DEVICES
+        );
+        for (shift, registers) in self.shift.iter().zip(self.registers.iter_mut()) {
+            let frame = Frame::from_bits(*shift);
+            registers.apply(frame);
+            self.frames.push(frame);
+        }
+        self.shift = [0; DEVICES];
+        self.bits = 0;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Line {
+    Data,
+    Cs,
+    Clk,
+}
+
+/// `DEVICES` fake MAX7219s chained together, connected to three [`MockPin`]s.
+#[derive(Debug)]
+pub struct MockMatrix<const DEVICES: usize = 1> {
+    bus: RefCell<Bus<DEVICES>>,
+}
+
+impl<const DEVICES: usize> Default for MockMatrix<DEVICES> {
+    fn default() -> Self {
+        Self::new_chained()
+    }
+}
+
+impl MockMatrix {
+    pub fn new() -> Self {
+        Self::new_chained()
+    }
+}
+
+impl<const DEVICES: usize> MockMatrix<DEVICES> {
+    pub fn new_chained() -> Self {
+        Self {
+            bus: RefCell::new(Bus::new()),
+        }
+    }
+
+    /// The `(data, cs, clk)` pins, in the order taken by `MAX7219::new`.
+    pub fn pins(
+        &self,
+    ) -> (
+        MockPin<'_, DEVICES>,
+        MockPin<'_, DEVICES>,
+        MockPin<'_, DEVICES>,
+    ) {
+        (
+            MockPin::new(&self.bus, Line::Data),
+            MockPin::new(&self.bus, Line::Cs),
+            MockPin::new(&self.bus, Line::Clk),
+        )
+    }
+
+    /// A `(spi, cs)` pair for `MAX7219::from_spi`.
+    pub fn spi(&self) -> (MockSpi<'_, DEVICES>, MockPin<'_, DEVICES>) {
+        (
+            MockSpi { bus: &self.bus },
+            MockPin::new(&self.bus, Line::Cs),
+        )
+    }
+
+    /// The registers of the first device in the chain.
+    pub fn registers(&self) -> Registers {
+        self.device_registers(0)
+    }
+
+    pub fn device_registers(&self, device: usize) -> Registers {
+        self.bus.borrow().registers[device]
+    }
+
+    /// The digits of the first device in the chain.
+    pub fn digits(&self) -> [u8; 8] {
+        self.registers().digits
+    }
+
+    /// Whether the LED at `(x, y)` is on, using the same layout as `MAX7219::write_pos`.
+    pub fn is_lit(&self, x: u8, y: u8) -> bool {
+        let x = x as usize % (8 * DEVICES);
+        let row = self.device_registers(x / 8).digits[7 - x % 8];
+        row & (1 << (y & 0x07)) != 0
+    }
+
+    /// Every frame latched so far, oldest first. Each latch adds one frame per device, starting
+    /// with the first device in the chain.
+    pub fn frames(&self) -> Vec<Frame> {
+        self.bus.borrow().frames.clone()
+    }
+
+    pub fn clear_frames(&self) {
+        self.bus.borrow_mut().frames.clear();
+    }
+
+    /// Every byte written through [`MockSpi`] so far, whether chip select was low or not.
+    pub fn spi_bytes(&self) -> Vec<u8> {
+        self.bus.borrow().spi_bytes.clone()
+    }
+}
+
+/// One of the data, chip select or clock lines of a [`MockMatrix`].
+#[derive(Debug)]
+pub struct MockPin<'a, const DEVICES: usize = 1> {
+    bus: &'a RefCell<Bus<DEVICES>>,
+    line: Line,
+}
+
+impl<'a, const DEVICES: usize> MockPin<'a, DEVICES> {
+    fn new(bus: &'a RefCell<Bus<DEVICES>>, line: Line) -> Self {
+        Self { bus, line }
+    }
+}
+
+impl<'a, const DEVICES: usize> OutputPin for MockPin<'a, DEVICES> {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().set(self.line, false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.bus.borrow_mut().set(self.line, true);
+        Ok(())
+    }
+}
+
+/// A SPI bus connected to the data and clock lines of a [`MockMatrix`].
+#[derive(Debug)]
+pub struct MockSpi<'a, const DEVICES: usize = 1> {
+    bus: &'a RefCell<Bus<DEVICES>>,
+}
+
+impl<'a, const DEVICES: usize> Write<u8> for MockSpi<'a, DEVICES> {
+    type Error = Infallible;
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let mut bus = self.bus.borrow_mut();
+        for &byte in words {
+            bus.write_spi(byte);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+//! Text in a 5x7 font for messages, labels and scores, scrolled across the display by
+//! [`ScrollText`](crate::animation::ScrollText).
+
+use snake_game::{progmem, progmem::ProgMem};
+
+const FIRST_CHAR: u8 = b' ';
+const LAST_CHAR: u8 = b'~';
+const GLYPH_COLUMNS: usize = 5;
+/// Columns taken up by each character, the glyph and the blank column after it.
+pub const CHAR_WIDTH: usize = GLYPH_COLUMNS + 1;
+
+/// How long each column of scrolling lasts unless told otherwise.
+pub const DEFAULT_COLUMN_MS: u16 = 80;
+
+progmem! {
+    /// The printable ASCII characters, five columns each, with the top row in the least significant
+    /// bit as 5x7 fonts are usually written.
+    static FONT: ProgMem<{ (LAST_CHAR - FIRST_CHAR + 1) as usize * GLYPH_COLUMNS }> = [
+        0x00, 0x00, 0x00, 0x00, 0x00, // space
+        0x00, 0x00, 0x5F, 0x00, 0x00, // !
+        0x00, 0x07, 0x00, 0x07, 0x00, // "
+        0x14, 0x7F, 0x14, 0x7F, 0x14, // #
+        0x24, 0x2A, 0x7F, 0x2A, 0x12, // $
+        0x23, 0x13, 0x08, 0x64, 0x62, // %
+        0x36, 0x49, 0x55, 0x22, 0x50, // &
+        0x00, 0x05, 0x03, 0x00, 0x00, // '
+        0x00, 0x1C, 0x22, 0x41, 0x00, // (
+        0x00, 0x41, 0x22, 0x1C, 0x00, // )
+        0x08, 0x2A, 0x1C, 0x2A, 0x08, // *
+        0x08, 0x08, 0x3E, 0x08, 0x08, // +
+        0x00, 0x50, 0x30, 0x00, 0x00, // ,
+        0x08, 0x08, 0x08, 0x08, 0x08, // -
+        0x00, 0x60, 0x60, 0x00, 0x00, // .
+        0x20, 0x10, 0x08, 0x04, 0x02, // /
+        0x3E, 0x51, 0x49, 0x45, 0x3E, // 0
+        0x00, 0x42, 0x7F, 0x40, 0x00, // 1
+        0x42, 0x61, 0x51, 0x49, 0x46, // 2
+        0x21, 0x41, 0x45, 0x4B, 0x31, // 3
+        0x18, 0x14, 0x12, 0x7F, 0x10, // 4
+        0x27, 0x45, 0x45, 0x45, 0x39, // 5
+        0x3C, 0x4A, 0x49, 0x49, 0x30, // 6
+        0x01, 0x71, 0x09, 0x05, 0x03, // 7
+        0x36, 0x49, 0x49, 0x49, 0x36, // 8
+        0x06, 0x49, 0x49, 0x29, 0x1E, // 9
+        0x00, 0x36, 0x36, 0x00, 0x00, // :
+        0x00, 0x56, 0x36, 0x00, 0x00, // ;
+        0x08, 0x14, 0x22, 0x41, 0x00, // <
+        0x14, 0x14, 0x14, 0x14, 0x14, // =
+        0x00, 0x41, 0x22, 0x14, 0x08, // >
+        0x02, 0x01, 0x51, 0x09, 0x06, // ?
+        0x32, 0x49, 0x79, 0x41, 0x3E, // @
+        0x7E, 0x11, 0x11, 0x11, 0x7E, // A
+        0x7F, 0x49, 0x49, 0x49, 0x36, // B
+        0x3E, 0x41, 0x41, 0x41, 0x22, // C
+        0x7F, 0x41, 0x41, 0x22, 0x1C, // D
+        0x7F, 0x49, 0x49, 0x49, 0x41, // E
+        0x7F, 0x09, 0x09, 0x09, 0x01, // F
+        0x3E, 0x41, 0x49, 0x49, 0x7A, // G
+        0x7F, 0x08, 0x08, 0x08, 0x7F, // H
+        0x00, 0x41, 0x7F, 0x41, 0x00, // I
+        0x20, 0x40, 0x41, 0x3F, 0x01, // J
+        0x7F, 0x08, 0x14, 0x22, 0x41, // K
+        0x7F, 0x40, 0x40, 0x40, 0x40, // L
+        0x7F, 0x02, 0x0C, 0x02, 0x7F, // M
+        0x7F, 0x04, 0x08, 0x10, 0x7F, // N
+        0x3E, 0x41, 0x41, 0x41, 0x3E, // O
+        0x7F, 0x09, 0x09, 0x09, 0x06, // P
+        0x3E, 0x41, 0x51, 0x21, 0x5E, // Q
+        0x7F, 0x09, 0x19, 0x29, 0x46, // R
+        0x46, 0x49, 0x49, 0x49, 0x31, // S
+        0x01, 0x01, 0x7F, 0x01, 0x01, // T
+        0x3F, 0x40, 0x40, 0x40, 0x3F, // U
+        0x1F, 0x20, 0x40, 0x20, 0x1F, // V
+        0x3F, 0x40, 0x38, 0x40, 0x3F, // W
+        0x63, 0x14, 0x08, 0x14, 0x63, // X
+        0x07, 0x08, 0x70, 0x08, 0x07, // Y
+        0x61, 0x51, 0x49, 0x45, 0x43, // Z
+        0x00, 0x7F, 0x41, 0x41, 0x00, // [
+        0x02, 0x04, 0x08, 0x10, 0x20, // \
+        0x00, 0x41, 0x41, 0x7F, 0x00, // ]
+        0x04, 0x02, 0x01, 0x02, 0x04, // ^
+        0x40, 0x40, 0x40, 0x40, 0x40, // _
+        0x00, 0x01, 0x02, 0x04, 0x00, // `
+        0x20, 0x54, 0x54, 0x54, 0x78, // a
+        0x7F, 0x48, 0x44, 0x44, 0x38, // b
+        0x38, 0x44, 0x44, 0x44, 0x20, // c
+        0x38, 0x44, 0x44, 0x48, 0x7F, // d
+        0x38, 0x54, 0x54, 0x54, 0x18, // e
+        0x08, 0x7E, 0x09, 0x01, 0x02, // f
+        0x0C, 0x52, 0x52, 0x52, 0x3E, // g
+        0x7F, 0x08, 0x04, 0x04, 0x78, // h
+        0x00, 0x44, 0x7D, 0x40, 0x00, // i
+        0x20, 0x40, 0x44, 0x3D, 0x00, // j
+        0x7F, 0x10, 0x28, 0x44, 0x00, // k
+        0x00, 0x41, 0x7F, 0x40, 0x00, // l
+        0x7C, 0x04, 0x18, 0x04, 0x78, // m
+        0x7C, 0x08, 0x04, 0x04, 0x78, // n
+        0x38, 0x44, 0x44, 0x44, 0x38, // o
+        0x7C, 0x14, 0x14, 0x14, 0x08, // p
+        0x08, 0x14, 0x14, 0x18, 0x7C, // q
+        0x7C, 0x08, 0x04, 0x04, 0x08, // r
+        0x48, 0x54, 0x54, 0x54, 0x20, // s
+        0x04, 0x3F, 0x44, 0x40, 0x20, // t
+        0x3C, 0x40, 0x40, 0x20, 0x7C, // u
+        0x1C, 0x20, 0x40, 0x20, 0x1C, // v
+        0x3C, 0x40, 0x30, 0x40, 0x3C, // w
+        0x44, 0x28, 0x10, 0x28, 0x44, // x
+        0x0C, 0x50, 0x50, 0x50, 0x3C, // y
+        0x44, 0x64, 0x54, 0x4C, 0x44, // z
+        0x00, 0x08, 0x36, 0x41, 0x00, // {
+        0x00, 0x00, 0x7F, 0x00, 0x00, // |
+        0x00, 0x41, 0x36, 0x08, 0x00, // }
+        0x08, 0x04, 0x08, 0x10, 0x08, // ~
+    ];
+}
+
+/// A string laid out in columns of the font. Anything outside of printable ASCII is drawn as `?`.
+#[derive(Debug, Clone, Copy)]
+pub struct Text<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Text<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+        }
+    }
+
+    /// The columns of the text, without the blank column after the last character.
+    pub fn width(&self) -> usize {
+        (self.bytes.len() * CHAR_WIDTH).saturating_sub(1)
+    }
+
+    /// Column `i` of the text as a row for
+    /// [`MAX7219::set_row`](crate::matrix_display::MAX7219::set_row), top of the glyph in the
+    /// most significant bit. Past the end of the text the columns are blank.
+    pub fn column(&self, i: usize) -> u8 {
+        let col = i % CHAR_WIDTH;
+        match self.bytes.get(i / CHAR_WIDTH) {
+            Some(&byte) if col < GLYPH_COLUMNS => {
+                let glyph = if (FIRST_CHAR..=LAST_CHAR).contains(&byte) {
+                    byte
+                } else {
+                    b'?'
+                };
+                FONT.load((glyph - FIRST_CHAR) as usize * GLYPH_COLUMNS + col)
+                    .reverse_bits()
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// A number written out in decimal, to be shown as [`Text`].
+#[derive(Debug, Clone, Copy)]
+pub struct Number {
+    digits: [u8; 5],
+    start: usize,
+}
+
+impl Number {
+    pub fn new(number: u16) -> Self {
+        let mut digits = [b'0'; 5];
+        let mut start = digits.len();
+        let mut rest = number;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (rest % 10) as u8;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+        Self { digits, start }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only ever ASCII digits
+        core::str::from_utf8(&self.digits[self.start..]).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Number, Text};
+
+    #[test]
+    fn glyphs() {
+        let text = Text::new("Hi!");
+        assert_eq!(text.width(), 17);
+        // H, with the top of the glyph in the top row
+        assert_eq!(text.column(0), 0b1111_1110);
+        assert_eq!(text.column(1), 0b0001_0000);
+        assert_eq!(text.column(4), 0b1111_1110);
+        assert_eq!(text.column(5), 0);
+        // i
+        assert_eq!(text.column(8), 0b1011_1110);
+        // ! and then nothing
+        assert_eq!(text.column(14), 0b1111_1010);
+        assert!((17..40).all(|i| text.column(i) == 0));
+
+        assert_eq!(Text::new("").width(), 0);
+        // Outside of the font
+        let unknown = Text::new("\t\u{e9}");
+        let question = Text::new("???");
+        assert_eq!(unknown.width(), question.width());
+        assert!((0..unknown.width()).all(|i| unknown.column(i) == question.column(i)));
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Number::new(0).as_str(), "0");
+        assert_eq!(Number::new(7).as_str(), "7");
+        assert_eq!(Number::new(120).as_str(), "120");
+        assert_eq!(Number::new(u16::MAX).as_str(), "65535");
+    }
+}
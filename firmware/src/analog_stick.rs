@@ -0,0 +1,123 @@
+use arduino_hal::{
+    adc::Channel,
+    port::{
+        mode::{Input, PullUp},
+        Pin,
+    },
+    Adc,
+};
+use ufmt::{derive::uDebug, uDisplay, uwrite};
+
+use snake_game::{
+    button::{Button, ButtonEvent},
+    calibration::Calibration,
+    direction::Direction,
+    entropy::EntropySource,
+    resolver::Resolver,
+};
+
+const MAX_ADC_READING: u16 = 0b11_1111_1111;
+
+pub struct AnalogStick<'adc> {
+    adc: &'adc mut Adc,
+    x_pin: Channel,
+    y_pin: Channel,
+    switch: Pin<Input<PullUp>>,
+    calibration: Calibration,
+    resolver: Resolver,
+    button: Button,
+}
+
+#[derive(Default, uDebug)]
+pub struct AnalogReading {
+    /// How far the stick is pushed, 0 inside the deadzone and up to `FULL_DEFLECTION` either way.
+    pub x: i16,
+    pub y: i16,
+    pub is_pressed: bool,
+    /// Where the stick is pointed, see [`Resolver`].
+    pub direction: Option<Direction>,
+}
+
+impl uDisplay for AnalogReading {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        let pressed_str = if self.is_pressed {
+            "Button Pressed"
+        } else {
+            "Button Not Pressed"
+        };
+        uwrite!(f, "X: {}, Y: {}, {}", self.x, self.y, pressed_str)
+    }
+}
+
+impl<'adc> AnalogStick<'adc> {
+    pub fn new(
+        adc: &'adc mut Adc,
+        x_pin: Channel,
+        y_pin: Channel,
+        switch: Pin<Input<PullUp>>,
+    ) -> Self {
+        Self {
+            adc,
+            x_pin,
+            y_pin,
+            switch,
+            calibration: Calibration::default(),
+            resolver: Resolver::new(),
+            button: Button::new(),
+        }
+    }
+
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// The x and y readings straight from the ADC, for calibrating.
+    pub fn get_raw(&mut self) -> (u16, u16) {
+        let x = self.adc.read_blocking(&self.x_pin);
+        let y = self.adc.read_blocking(&self.y_pin);
+        (x.min(MAX_ADC_READING), y.min(MAX_ADC_READING))
+    }
+
+    pub fn get_reading(&mut self) -> AnalogReading {
+        let (x, y) = self.get_raw();
+        let x = self.calibration.x.deflection(x);
+        let y = self.calibration.y.deflection(y);
+        let is_pressed = self.switch.is_low();
+        // Pushing the stick right lowers the x reading
+        let direction = self.resolver.resolve(-x, y);
+
+        AnalogReading {
+            x,
+            y,
+            is_pressed,
+            direction,
+        }
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        self.switch.is_low()
+    }
+
+    /// Reads the switch for the button, `now_ms` being the time on the clock. Has to be called
+    /// every few milliseconds, see [`Button`].
+    pub fn button_event(&mut self, now_ms: u32) -> Option<ButtonEvent> {
+        let is_pressed = self.is_pressed();
+        self.button.update(is_pressed, now_ms)
+    }
+}
+
+/// The stick jitters by a few counts even when left alone.
+impl EntropySource for AnalogStick<'_> {
+    fn sample(&mut self) -> u16 {
+        let x = self.adc.read_blocking(&self.x_pin);
+        let y = self.adc.read_blocking(&self.y_pin);
+        x ^ y.rotate_left(8)
+    }
+}
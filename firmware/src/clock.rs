@@ -0,0 +1,68 @@
+//! A millisecond clock counted by the TC1 compare interrupt, which also raises a flag every
+//! `TICK_MS` for the game loop to run on.
+
+use core::cell::Cell;
+
+use arduino_hal::pac::TC1;
+use avr_device::interrupt::{self, Mutex};
+
+use snake_game::clock::Clock;
+
+/// How often the game loop runs, reading the stick and checking what is due.
+pub const TICK_MS: u8 = 10;
+
+// With the 16MHz clock divided by 64, a compare match every millisecond
+const TIMER_COUNTS: u16 = 250;
+
+static MILLIS: Mutex<Cell<u32>> = Mutex::new(Cell::new(0));
+// Milliseconds left until the next tick
+static UNTIL_TICK: Mutex<Cell<u8>> = Mutex::new(Cell::new(TICK_MS));
+static TICKED: Mutex<Cell<bool>> = Mutex::new(Cell::new(false));
+
+/// TC1 counting milliseconds, there is only ever one.
+pub struct Millis {
+    _timer: TC1,
+}
+
+impl Millis {
+    /// Starts the timer and enables interrupts.
+    pub fn start(timer: TC1) -> Self {
+        // CTC mode 4, counting up to OCR1A and back to 0
+        timer.tccr1a.write(|w| w.wgm1().bits(0b00));
+        timer.ocr1a.write(|w| w.bits(TIMER_COUNTS - 1));
+        timer
+            .tccr1b
+            .write(|w| w.wgm1().bits(0b01).cs1().prescale_64());
+        timer.timsk1.write(|w| w.ocie1a().set_bit());
+        // SAFETY: nothing else is in a critical section yet
+        unsafe { interrupt::enable() };
+        Self { _timer: timer }
+    }
+
+    /// Waits for the next tick, returning straight away if it was missed while busy.
+    pub fn wait_tick(&self) {
+        while !interrupt::free(|cs| TICKED.borrow(cs).replace(false)) {}
+    }
+}
+
+impl Clock for Millis {
+    fn now_ms(&self) -> u32 {
+        interrupt::free(|cs| MILLIS.borrow(cs).get())
+    }
+}
+
+#[avr_device::interrupt(atmega2560)]
+fn TIMER1_COMPA() {
+    interrupt::free(|cs| {
+        let millis = MILLIS.borrow(cs);
+        millis.set(millis.get().wrapping_add(1));
+
+        let until_tick = UNTIL_TICK.borrow(cs);
+        if until_tick.get() <= 1 {
+            until_tick.set(TICK_MS);
+            TICKED.borrow(cs).set(true);
+        } else {
+            until_tick.set(until_tick.get() - 1);
+        }
+    });
+}
@@ -0,0 +1,21 @@
+use arduino_hal::{adc::Channel, Adc};
+
+use snake_game::entropy::EntropySource;
+
+/// An analog pin left unconnected, its readings pick up noise from the surroundings.
+pub struct FloatingPin<'adc> {
+    adc: &'adc mut Adc,
+    channel: Channel,
+}
+
+impl<'adc> FloatingPin<'adc> {
+    pub fn new(adc: &'adc mut Adc, channel: Channel) -> Self {
+        Self { adc, channel }
+    }
+}
+
+impl EntropySource for FloatingPin<'_> {
+    fn sample(&mut self) -> u16 {
+        self.adc.read_blocking(&self.channel)
+    }
+}
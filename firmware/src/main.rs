@@ -0,0 +1,374 @@
+#![no_std]
+#![no_main]
+#![feature(panic_info_message)]
+#![feature(panic_internals)]
+#![feature(abi_avr_interrupt)]
+
+mod analog_stick;
+mod clock;
+mod entropy;
+mod panic_handler;
+mod storage;
+
+// use panic_halt as _;
+use arduino_hal::{delay_ms, prelude::*};
+use snake_display::{
+    animation::{Animation, Flash, ScrollText, Wipe},
+    matrix_display::MAX7219,
+    text::{Number, DEFAULT_COLUMN_MS},
+};
+use snake_game::{
+    button::ButtonEvent,
+    calibration::{Calibration, Calibrator},
+    clock::{Clock, Ticker},
+    direction::Direction,
+    entropy::Entropy,
+    highscores::HighScores,
+    position::Boundary,
+    replay::Recording,
+    turns::TurnQueue,
+    Command, Game, GameResult, Mode,
+};
+
+// How many of the best scores are kept in the EEPROM
+const HIGH_SCORES: usize = 10;
+const _: () = assert!(
+    storage::HIGH_SCORES_ADDR as usize + HighScores::<HIGH_SCORES>::SIZE
+        <= storage::CALIBRATION_ADDR as usize
+);
+// Readings of the stick at rest, 10ms apart
+const CALIBRATION_REST_SAMPLES: u16 = 100;
+// How long the head of a paused snake is lit, then dark
+const PAUSED_BLINK_MS: u16 = 300;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+    ufmt::uwriteln!(&mut serial, "Starting...").void_unwrap();
+
+    let mut storage = storage::EepromStorage::new(arduino_hal::Eeprom::new(dp.EEPROM));
+    let mut high_scores: HighScores<HIGH_SCORES> =
+        HighScores::load(&mut storage, storage::HIGH_SCORES_ADDR);
+    for (rank, entry) in high_scores.iter().enumerate() {
+        ufmt::uwriteln!(
+            &mut serial,
+            "#{}: {} apples in {} ticks",
+            rank + 1,
+            entry.score.apples,
+            entry.score.ticks
+        )
+        .void_unwrap();
+    }
+    let mut adc = arduino_hal::adc::Adc::new(dp.ADC, Default::default());
+    // A3 is left unconnected, the noise it picks up is the first part of the seed
+    let mut entropy = Entropy::new();
+    let floating = pins.a3.into_analog_input(&mut adc).into_channel();
+    entropy.gather(&mut entropy::FloatingPin::new(&mut adc, floating), 64);
+    let x_pin = pins.a0.into_analog_input(&mut adc).into_channel();
+    let y_pin = pins.a1.into_analog_input(&mut adc).into_channel();
+    let switch = pins.a2.into_pull_up_input().downgrade();
+    let mut stick = analog_stick::AnalogStick::new(&mut adc, x_pin, y_pin, switch);
+
+    #[cfg(not(feature = "hardware-spi"))]
+    let mut display = {
+        let clk = pins.d22.into_output();
+        let cs = pins.d23.into_output();
+        let data = pins.d24.into_output();
+        MAX7219::new(data, cs, clk)
+    };
+    // DIN on 51 (MOSI), CLK on 52 (SCK) and CS on 53 (SS)
+    #[cfg(feature = "hardware-spi")]
+    let mut display = {
+        let settings = arduino_hal::spi::Settings {
+            data_order: arduino_hal::spi::DataOrder::MostSignificantFirst,
+            clock: arduino_hal::spi::SerialClockRate::OscfOver4,
+            mode: embedded_hal::spi::MODE_0,
+        };
+        let (spi, cs) = arduino_hal::Spi::new(
+            dp.SPI,
+            pins.d52.into_output(),
+            pins.d51.into_output(),
+            pins.d50.into_pull_up_input(),
+            pins.d53.into_output(),
+            settings,
+        );
+        MAX7219::from_spi(spi, cs)
+    };
+    display.power_on();
+    display.set_intensity(1);
+
+    // Holding the button at power on calibrates the stick, otherwise the saved calibration is used
+    if stick.is_pressed() {
+        ufmt::uwriteln!(&mut serial, "Calibrating, let go of the stick...").void_unwrap();
+        // Lit while the stick should be left alone
+        display.flash(true);
+        while stick.is_pressed() {
+            delay_ms(10);
+        }
+        delay_ms(500);
+        let mut calibrator = Calibrator::new();
+        for _ in 0..CALIBRATION_REST_SAMPLES {
+            let (x, y) = stick.get_raw();
+            calibrator.sample_rest(x, y);
+            delay_ms(10);
+        }
+        display.flash(false);
+        ufmt::uwriteln!(
+            &mut serial,
+            "Push the stick all the way round, then press the button..."
+        )
+        .void_unwrap();
+        while !stick.is_pressed() {
+            let (x, y) = stick.get_raw();
+            calibrator.sample_moving(x, y);
+        }
+        match calibrator.finish() {
+            Some(calibration) => {
+                calibration.save(&mut storage, storage::CALIBRATION_ADDR);
+                stick.set_calibration(calibration);
+            }
+            None => {
+                ufmt::uwriteln!(&mut serial, "Stick hardly moved, not saved").void_unwrap();
+            }
+        }
+        while stick.is_pressed() {
+            delay_ms(10);
+        }
+    } else if let Some(calibration) = Calibration::load(&mut storage, storage::CALIBRATION_ADDR) {
+        stick.set_calibration(calibration);
+    }
+    let calibration = stick.calibration();
+    for (name, axis) in [("x", calibration.x), ("y", calibration.y)] {
+        ufmt::uwriteln!(
+            &mut serial,
+            "Stick {}: center {}, span {}, deadzone {}",
+            name,
+            axis.center,
+            axis.span,
+            axis.deadzone
+        )
+        .void_unwrap();
+    }
+
+    // The rest of the seed is the stick jitter and how long it takes to press the button
+    ufmt::uwriteln!(&mut serial, "Press the button to start...").void_unwrap();
+    // Holding the stick while pressing picks the mode: up for walls at the edges, down for the
+    // levels and left or right for both
+    let mut polls: u16 = 0;
+    let mode = loop {
+        let reading = stick.get_reading();
+        if reading.is_pressed {
+            let (boundary, levels) = match reading.direction {
+                None => (Boundary::Wrap, false),
+                Some(Direction::Up) => (Boundary::Walls, false),
+                Some(Direction::Down) => (Boundary::Wrap, true),
+                Some(Direction::Left | Direction::Right) => (Boundary::Walls, true),
+            };
+            break Mode { boundary, levels };
+        }
+        entropy.gather(&mut stick, 1);
+        polls = polls.wrapping_add(1);
+    };
+    entropy.mix(polls);
+    // Otherwise the press would reset the first game
+    while stick.get_reading().is_pressed {
+        delay_ms(10);
+    }
+    let seed = entropy.seed();
+    ufmt::uwriteln!(&mut serial, "Seed: {}", seed).void_unwrap();
+
+    // The current game, written out when the snake dies so it can be replayed on a host
+    let mut recording: Recording<128> = Recording::with_mode(seed, mode);
+    // A single 8x8 matrix, the default board
+    let mut game: Game = recording.new_game();
+    // Saved as soon as a game ends, the power could go at any time
+    let mut record_score = |score, mode| {
+        let rank = high_scores.insert(score, mode);
+        if rank.is_some() {
+            high_scores.save(&mut storage, storage::HIGH_SCORES_ADDR);
+        }
+        rank
+    };
+    let clock = clock::Millis::start(dp.TC1);
+    // Every turn read between steps, taken one per step
+    let mut turns: TurnQueue<3> = TurnQueue::new();
+    // A long press resets the game on the next step
+    let mut reset = false;
+    // The head blinks while the game is paused
+    let mut blink = Ticker::new(&clock, PAUSED_BLINK_MS);
+    let mut head_lit = true;
+    // Steps are scheduled on the clock, so the time spent printing and drawing is taken out of
+    // the wait for the next step instead of adding to it
+    let mut steps = Ticker::new(&clock, game.tick_interval_ms());
+    // Played instead of stepping the game until it ends or a press skips it
+    let mut animation: Option<(Animation, Ticker)> = None;
+
+    loop {
+        clock.wait_tick();
+        let reading = stick.get_reading();
+        let event = stick.button_event(clock.now_ms());
+        if let Some(event) = event {
+            ufmt::uwriteln!(&mut serial, "Button: {:?}", event).void_unwrap();
+        }
+
+        if let Some((playing, frames)) = animation.as_mut() {
+            // Skipped once the press is over, so the game doesn't take it as well
+            let skipped = matches!(event, Some(ev) if ev != ButtonEvent::Pressed);
+            if skipped || (frames.poll(&clock) && !playing.advance(&mut display)) {
+                animation = None;
+                display.clear_display();
+                steps.reset(&clock);
+            }
+            continue;
+        }
+
+        match event {
+            Some(ButtonEvent::LongPress) => reset = true,
+            // A short press pauses the game until the next one, it is only sent once it can't be
+            // the start of a double click
+            Some(ButtonEvent::Released) => {
+                let cmd = if game.is_paused() {
+                    Command::Resume
+                } else {
+                    Command::Pause
+                };
+                recording.push(cmd);
+                if game.step(cmd) == GameResult::Paused {
+                    ufmt::uwriteln!(&mut serial, "Paused...").void_unwrap();
+                } else {
+                    ufmt::uwriteln!(&mut serial, "Resuming...").void_unwrap();
+                }
+                steps.reset(&clock);
+                blink.reset(&clock);
+                head_lit = true;
+                if let Some(head) = game.iter_snake().last() {
+                    display.set_pixel(head.x(), head.y(), true);
+                    display.flush();
+                }
+            }
+            _ => {}
+        }
+        // Unless a reset is waiting for the next step
+        if game.is_paused() && !reset {
+            // Only the head changes, the rest of the frame is left as it was last drawn
+            if blink.poll(&clock) {
+                head_lit = !head_lit;
+                if let Some(head) = game.iter_snake().last() {
+                    display.set_pixel(head.x(), head.y(), head_lit);
+                    display.flush();
+                }
+            }
+            continue;
+        }
+        if let Some(dir) = reading.direction {
+            turns.push(dir, game.heading());
+        }
+        if !steps.poll(&clock) {
+            continue;
+        }
+        let direction = turns.next(game.heading());
+        let cmd = if core::mem::take(&mut reset) {
+            Command::Reset
+        } else {
+            Command::Move(direction)
+        };
+        ufmt::uwriteln!(serial, "Command: {:?}", direction).void_unwrap();
+
+        ufmt::uwriteln!(&mut serial, "Stepping...").void_unwrap();
+        recording.push(cmd);
+        let result = game.step(cmd);
+        if result != GameResult::Continue {
+            // Meant for the snake which just went
+            turns.clear();
+        }
+        let ended = matches!(
+            result,
+            GameResult::Died | GameResult::Won | GameResult::Restarting
+        );
+        let started: Option<Animation> = match result {
+            GameResult::Continue => {
+                ufmt::uwriteln!(&mut serial, "Moving...").void_unwrap();
+                None
+            }
+            // Steps are held off while paused
+            GameResult::Paused => None,
+            GameResult::Died => {
+                ufmt::uwriteln!(&mut serial, "Died...").void_unwrap();
+                if recording.is_full() {
+                    ufmt::uwriteln!(&mut serial, "Recording full, truncated").void_unwrap();
+                }
+                ufmt::uwriteln!(&mut serial, "Recording: {}", recording).void_unwrap();
+                let (score, best) = (game.last_score(), game.best_score());
+                ufmt::uwriteln!(
+                    &mut serial,
+                    "Score: {} apples in {} ticks, best: {}",
+                    score.apples,
+                    score.ticks,
+                    best.apples
+                )
+                .void_unwrap();
+                if let Some(rank) = record_score(score, game.mode()) {
+                    ufmt::uwriteln!(&mut serial, "New high score: #{}", rank + 1).void_unwrap();
+                }
+                ScrollText::new("LOSE ", DEFAULT_COLUMN_MS).map(|mut message| {
+                    message.push_str(Number::new(score.apples).as_str());
+                    message.into()
+                })
+            }
+            GameResult::Won => {
+                ufmt::uwriteln!(&mut serial, "Won...").void_unwrap();
+                if let Some(rank) = record_score(game.last_score(), game.mode()) {
+                    ufmt::uwriteln!(&mut serial, "New high score: #{}", rank + 1).void_unwrap();
+                }
+                ScrollText::new("WIN ", DEFAULT_COLUMN_MS).map(|mut message| {
+                    message.push_str(Number::new(game.last_score().apples).as_str());
+                    message.into()
+                })
+            }
+            GameResult::NextLevel => {
+                ufmt::uwriteln!(&mut serial, "Next level...").void_unwrap();
+                Some(Wipe::new(40).into())
+            }
+            GameResult::Restarting => {
+                ufmt::uwriteln!(&mut serial, "Restarting...").void_unwrap();
+                // A reset ends the game too, as long as an apple was eaten it counts
+                if let Some(rank) = record_score(game.last_score(), game.mode()) {
+                    ufmt::uwriteln!(&mut serial, "New high score: #{}", rank + 1).void_unwrap();
+                }
+                Some(Flash::new(2, 500).into())
+            }
+        };
+        if ended {
+            // Starting from the seed of the next snake, a recording since power on would be full
+            // after a couple of games
+            recording = Recording::of_game(&game);
+        }
+        // The game speeds up as the snake grows
+        steps.set_interval(game.tick_interval_ms());
+
+        ufmt::uwriteln!(&mut serial, "Printing...").void_unwrap();
+        ufmt::uwriteln!(&mut serial, "Game:\n{}", game).void_unwrap();
+        if let Some(mut playing) = started {
+            // The first frame straight away, the rest on every tick of their own
+            playing.advance(&mut display);
+            let frames = Ticker::new(&clock, playing.frame_ms());
+            animation = Some((playing, frames));
+            continue;
+        }
+
+        // Draw the whole frame off-screen, only the rows which changed are sent
+        display.clear_frame();
+        for pos in game.board().walls() {
+            display.set_pixel(pos.x(), pos.y(), true);
+        }
+        let apple = game.apple();
+        display.set_pixel(apple.x(), apple.y(), true);
+        for pos in game.iter_snake() {
+            display.set_pixel(pos.x(), pos.y(), true);
+        }
+        display.flush();
+    }
+}
@@ -0,0 +1,36 @@
+use arduino_hal::Eeprom;
+
+use snake_game::storage::Storage;
+
+/// Where the high scores are kept in the 4 KiB of EEPROM.
+pub const HIGH_SCORES_ADDR: u16 = 0;
+/// Where the stick calibration is kept, leaving the high scores room to grow.
+pub const CALIBRATION_ADDR: u16 = 256;
+
+/// The EEPROM of the ATmega2560.
+pub struct EepromStorage {
+    eeprom: Eeprom,
+}
+
+impl EepromStorage {
+    pub fn new(eeprom: Eeprom) -> Self {
+        Self { eeprom }
+    }
+}
+
+impl Storage for EepromStorage {
+    fn read(&mut self, addr: u16, buf: &mut [u8]) {
+        for (offset, byte) in (addr..).zip(buf.iter_mut()) {
+            *byte = self.eeprom.read_byte(offset);
+        }
+    }
+
+    fn write(&mut self, addr: u16, data: &[u8]) {
+        // Every cell only lasts around 100,000 writes, so leave the unchanged ones alone
+        for (offset, &byte) in (addr..).zip(data.iter()) {
+            if self.eeprom.read_byte(offset) != byte {
+                self.eeprom.write_byte(offset, byte);
+            }
+        }
+    }
+}
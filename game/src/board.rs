@@ -0,0 +1,105 @@
+use super::{
+    direction::Direction,
+    level::Level,
+    occupancy::Occupancy,
+    position::{Boundary, Position},
+};
+
+/// Where the snake can go: what happens at the edges and the walls of the current level.
+#[derive(Debug, Clone)]
+pub struct Board<const X_LIM: isize, const Y_LIM: isize> {
+    boundary: Boundary,
+    walls: Occupancy<X_LIM, Y_LIM>,
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> Board<X_LIM, Y_LIM> {
+    /// A board without any walls.
+    pub fn new(boundary: Boundary) -> Self {
+        Self {
+            boundary,
+            walls: Occupancy::new(),
+        }
+    }
+
+    /// Replaces the walls with those of `level`, or removes them all for `None`.
+    pub fn load(&mut self, level: Option<Level>) {
+        self.walls.clear();
+        if let Some(level) = level {
+            for pos in self.cells() {
+                if level.is_wall(pos.x() as usize, pos.y() as usize) {
+                    self.walls.set(&pos, true);
+                }
+            }
+        }
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    #[inline]
+    pub fn is_wall(&self, pos: &Position<X_LIM, Y_LIM>) -> bool {
+        self.walls.is_set(pos)
+    }
+
+    /// Every wall cell, row by row.
+    pub fn walls(&self) -> impl Iterator<Item = Position<X_LIM, Y_LIM>> + '_ {
+        self.cells().filter(|pos| self.is_wall(pos))
+    }
+
+    /// The cell `scale` steps in `dir`, `None` if that is a wall or off the board.
+    pub fn offset(
+        &self,
+        pos: &Position<X_LIM, Y_LIM>,
+        dir: Direction,
+        scale: isize,
+    ) -> Option<Position<X_LIM, Y_LIM>> {
+        pos.offset_dir_bounded(dir, scale, self.boundary)
+            .filter(|pos| !self.is_wall(pos))
+    }
+
+    /// Whether the `len` cells from `pos` in `dir` are all open, `pos` itself included.
+    pub fn is_clear(&self, pos: &Position<X_LIM, Y_LIM>, dir: Direction, len: isize) -> bool {
+        !self.is_wall(pos) && (1..len).all(|i| self.offset(pos, dir, i).is_some())
+    }
+
+    fn cells(&self) -> impl Iterator<Item = Position<X_LIM, Y_LIM>> {
+        (0..Y_LIM).flat_map(|y| (0..X_LIM).map(move |x| Position::new(x as u8, y as u8)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Board;
+    use crate::{
+        direction::Direction,
+        level::Level,
+        position::{Boundary, Position},
+    };
+
+    #[test]
+    fn walls_block() {
+        let mut board = Board::<8, 8>::new(Boundary::Wrap);
+        let corner = Position::new(0, 7);
+        assert_eq!(board.walls().count(), 0);
+        assert_eq!(
+            board.offset(&corner, Direction::Up, 1),
+            Some(Position::new(0, 0))
+        );
+
+        // Corners, with three walls in each corner of the board
+        board.load(Some(Level::first().next().unwrap().next().unwrap()));
+        assert_eq!(board.walls().count(), 12);
+        assert!(board.is_wall(&corner));
+        assert_eq!(board.offset(&Position::new(2, 7), Direction::Left, 1), None);
+        assert_eq!(
+            board.offset(&Position::new(2, 7), Direction::Down, 1),
+            Some(Position::new(2, 6))
+        );
+        assert!(board.is_clear(&Position::new(2, 5), Direction::Right, 4));
+        assert!(!board.is_clear(&Position::new(2, 6), Direction::Left, 3));
+
+        board.load(None);
+        assert_eq!(board.walls().count(), 0);
+    }
+}
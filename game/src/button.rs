@@ -0,0 +1,223 @@
+//! Turning the readings of a push button into presses. The switch bounces for a few milliseconds
+//! whenever it opens or closes, so a reading only counts once it has held for `DEBOUNCE_MS`.
+
+use ufmt::derive::uDebug;
+
+/// How long the switch has to read the same before a change counts.
+pub const DEBOUNCE_MS: u32 = 30;
+/// How long the button has to be held for a `LongPress`.
+pub const LONG_PRESS_MS: u32 = 800;
+/// From letting go of one press to the start of the next for a `DoubleClick`.
+pub const DOUBLE_CLICK_MS: u32 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uDebug)]
+pub enum ButtonEvent {
+    Pressed,
+    /// A short press, sent `DOUBLE_CLICK_MS` after it was let go of once it is clear it wasn't
+    /// the first of a `DoubleClick`.
+    Released,
+    /// Held down for `LONG_PRESS_MS`, nothing more comes of that press.
+    LongPress,
+    /// Pressed again within `DOUBLE_CLICK_MS` of a short press, in place of `Pressed`. Neither
+    /// press sends a `Released`.
+    DoubleClick,
+}
+
+/// Debounces a button and turns it into [`ButtonEvent`]s, fed a reading and the time it was
+/// taken every few milliseconds.
+#[derive(Debug, Default)]
+pub struct Button {
+    // The last reading and when it changed
+    raw: bool,
+    raw_since: u32,
+    // The state once debounced
+    down: bool,
+    pressed_at: u32,
+    // When a short press was let go of, until it is sent or turns into a double click
+    released_at: Option<u32>,
+    // The current press has already been taken as a long press or a double click
+    handled: bool,
+}
+
+impl Button {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, is_pressed: bool, now_ms: u32) -> Option<ButtonEvent> {
+        if is_pressed != self.raw {
+            self.raw = is_pressed;
+            self.raw_since = now_ms;
+        }
+
+        if self.raw != self.down && now_ms.wrapping_sub(self.raw_since) >= DEBOUNCE_MS {
+            self.down = self.raw;
+            if self.down {
+                self.pressed_at = now_ms;
+                let double = self.released_at.take().is_some();
+                self.handled = double;
+                return Some(if double {
+                    ButtonEvent::DoubleClick
+                } else {
+                    ButtonEvent::Pressed
+                });
+            }
+            if !self.handled {
+                self.released_at = Some(now_ms);
+            }
+            return None;
+        }
+
+        match self.released_at {
+            Some(at) if now_ms.wrapping_sub(at) > DOUBLE_CLICK_MS => {
+                self.released_at = None;
+                Some(ButtonEvent::Released)
+            }
+            _ if self.down
+                && !self.handled
+                && now_ms.wrapping_sub(self.pressed_at) >= LONG_PRESS_MS =>
+            {
+                self.handled = true;
+                Some(ButtonEvent::LongPress)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Button,
+        ButtonEvent::{self, *},
+        DEBOUNCE_MS, DOUBLE_CLICK_MS, LONG_PRESS_MS,
+    };
+
+    /// Feeds `button` a reading every millisecond from `start`, holding each of `presses` for as
+    /// long as given. Returns every event with the time it was sent.
+    fn feed(button: &mut Button, start: u32, presses: &[(bool, u32)]) -> Vec<(u32, ButtonEvent)> {
+        let mut now = start;
+        let mut events = Vec::new();
+        for &(is_pressed, ms) in presses {
+            for _ in 0..ms {
+                if let Some(event) = button.update(is_pressed, now) {
+                    events.push((now.wrapping_sub(start), event));
+                }
+                now = now.wrapping_add(1);
+            }
+        }
+        events
+    }
+
+    fn events(presses: &[(bool, u32)]) -> Vec<ButtonEvent> {
+        feed(&mut Button::new(), 1000, presses)
+            .into_iter()
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    #[test]
+    fn debounces() {
+        // Bouncing closed then held
+        let bounce = [(true, 5), (false, 3), (true, 10), (false, 2), (true, 100)];
+        let sent = feed(&mut Button::new(), 1000, &bounce);
+        assert_eq!(sent, [(20 + DEBOUNCE_MS, Pressed)]);
+        // Glitches shorter than the debounce don't count at all
+        let glitches = [
+            (true, DEBOUNCE_MS - 1),
+            (false, 50),
+            (true, 10),
+            (false, 500),
+        ];
+        assert_eq!(events(&glitches), []);
+        // Nor do they end a press
+        let held = [
+            (true, 100),
+            (false, DEBOUNCE_MS - 1),
+            (true, 100),
+            (false, 500),
+        ];
+        assert_eq!(events(&held), [Pressed, Released]);
+    }
+
+    #[test]
+    fn short_press() {
+        let sent = feed(&mut Button::new(), 1000, &[(true, 100), (false, 500)]);
+        // Only once a double click is ruled out
+        assert_eq!(
+            sent,
+            [
+                (DEBOUNCE_MS, Pressed),
+                (100 + DEBOUNCE_MS + DOUBLE_CLICK_MS + 1, Released)
+            ]
+        );
+    }
+
+    #[test]
+    fn long_press() {
+        let sent = feed(&mut Button::new(), 1000, &[(true, 2000), (false, 1000)]);
+        assert_eq!(
+            sent,
+            [
+                (DEBOUNCE_MS, Pressed),
+                (DEBOUNCE_MS + LONG_PRESS_MS, LongPress)
+            ]
+        );
+        // Let go just before it becomes one
+        let short = [(true, LONG_PRESS_MS - 1), (false, 1000)];
+        assert_eq!(events(&short), [Pressed, Released]);
+        // A press right after a long one is not a double click
+        let again = [(true, 1000), (false, 100), (true, 100), (false, 1000)];
+        assert_eq!(events(&again), [Pressed, LongPress, Pressed, Released]);
+    }
+
+    #[test]
+    fn double_click() {
+        let quick = [(true, 100), (false, 200), (true, 100), (false, 1000)];
+        assert_eq!(events(&quick), [Pressed, DoubleClick]);
+        let slow = [
+            (true, 100),
+            (false, DOUBLE_CLICK_MS + 50),
+            (true, 100),
+            (false, 1000),
+        ];
+        assert_eq!(events(&slow), [Pressed, Released, Pressed, Released]);
+        // A third press starts over
+        let triple = [
+            (true, 100),
+            (false, 100),
+            (true, 100),
+            (false, 100),
+            (true, 100),
+            (false, 1000),
+        ];
+        assert_eq!(events(&triple), [Pressed, DoubleClick, Pressed, Released]);
+        // Holding the second press doesn't make it a long press as well
+        let held = [(true, 100), (false, 100), (true, 2000), (false, 1000)];
+        assert_eq!(events(&held), [Pressed, DoubleClick]);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let start = u32::MAX - 150;
+        let mut button = Button::new();
+        let sent = feed(
+            &mut button,
+            start,
+            &[(true, 100), (false, 200), (true, 100)],
+        );
+        assert_eq!(
+            sent,
+            [(DEBOUNCE_MS, Pressed), (300 + DEBOUNCE_MS, DoubleClick)]
+        );
+        let mut button = Button::new();
+        let sent = feed(&mut button, start, &[(true, 2000)]);
+        assert_eq!(
+            sent,
+            [
+                (DEBOUNCE_MS, Pressed),
+                (DEBOUNCE_MS + LONG_PRESS_MS, LongPress)
+            ]
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Joystick calibration: where each axis rests, how far it moves and how much it wanders while
+//! left alone, measured once and saved alongside the high scores.
+//!
+//! Saved as `MAGIC`, `VERSION`, the center, span and deadzone of x then y (u16, LE) and a
+//! Fletcher-16 of everything before it.
+
+use super::storage::{Fletcher16, Storage};
+
+const MAGIC: u8 = b'J';
+// Bump whenever the layout changes
+const VERSION: u8 = 1;
+const AXIS_BYTES: usize = 6;
+const RECORD_BYTES: usize = 2 + 2 * AXIS_BYTES;
+
+/// The furthest an axis can be pushed, as returned by [`Axis::deflection`].
+pub const FULL_DEFLECTION: i16 = 100;
+// A stick which moves less than this from its center in either direction hasn't been moved at all
+const MIN_SPAN: u16 = 64;
+
+/// One axis of the stick, in raw ADC readings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Axis {
+    /// The reading at rest.
+    pub center: u16,
+    /// How far the reading goes from `center`, the shorter of the two directions.
+    pub span: u16,
+    /// Readings this close to `center` count as being at rest.
+    pub deadzone: u16,
+}
+
+impl Default for Axis {
+    /// What the stick used to be assumed to do: rest around 500 and go a quarter of the range
+    /// either side before counting as pushed.
+    fn default() -> Self {
+        Self {
+            center: 500,
+            span: 500,
+            deadzone: 250,
+        }
+    }
+}
+
+impl Axis {
+    /// How far `raw` is pushed from the center, from `-FULL_DEFLECTION` to `FULL_DEFLECTION`.
+    /// Inside the deadzone it is 0, and the rest of the span is scaled to fit.
+    pub fn deflection(&self, raw: u16) -> i16 {
+        let offset = raw as i32 - self.center as i32;
+        let distance = offset.unsigned_abs().saturating_sub(self.deadzone as u32);
+        let range = self.span.saturating_sub(self.deadzone).max(1) as u32;
+        // Rounded up, so anything outside of the deadzone counts
+        let scaled =
+            ((distance * FULL_DEFLECTION as u32 + range - 1) / range).min(FULL_DEFLECTION as u32);
+        scaled as i16 * offset.signum() as i16
+    }
+
+    fn encode(&self, buf: &mut [u8]) {
+        buf[..2].copy_from_slice(&self.center.to_le_bytes());
+        buf[2..4].copy_from_slice(&self.span.to_le_bytes());
+        buf[4..6].copy_from_slice(&self.deadzone.to_le_bytes());
+    }
+
+    fn decode(buf: &[u8]) -> Self {
+        Self {
+            center: u16::from_le_bytes([buf[0], buf[1]]),
+            span: u16::from_le_bytes([buf[2], buf[3]]),
+            deadzone: u16::from_le_bytes([buf[4], buf[5]]),
+        }
+    }
+}
+
+/// Both axes of the stick.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub x: Axis,
+    pub y: Axis,
+}
+
+impl Calibration {
+    /// The bytes taken up in storage.
+    pub const SIZE: usize = RECORD_BYTES + 2;
+
+    /// Reads the calibration saved at `addr`, `None` if there isn't a valid one there.
+    pub fn load<S: Storage>(storage: &mut S, addr: u16) -> Option<Self> {
+        let mut record = [0; RECORD_BYTES];
+        storage.read(addr, &mut record);
+        let mut checksum = [0; 2];
+        storage.read(addr + RECORD_BYTES as u16, &mut checksum);
+
+        let mut sum = Fletcher16::default();
+        sum.update(&record);
+        if record[0] != MAGIC || record[1] != VERSION || u16::from_le_bytes(checksum) != sum.value()
+        {
+            return None;
+        }
+        Some(Self {
+            x: Axis::decode(&record[2..]),
+            y: Axis::decode(&record[2 + AXIS_BYTES..]),
+        })
+    }
+
+    /// Writes the calibration at `addr`, taking up `SIZE` bytes.
+    pub fn save<S: Storage>(&self, storage: &mut S, addr: u16) {
+        let mut record = [0; RECORD_BYTES];
+        record[0] = MAGIC;
+        record[1] = VERSION;
+        self.x.encode(&mut record[2..]);
+        self.y.encode(&mut record[2 + AXIS_BYTES..]);
+        let mut sum = Fletcher16::default();
+        sum.update(&record);
+        storage.write(addr, &record);
+        storage.write(addr + RECORD_BYTES as u16, &sum.value().to_le_bytes());
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AxisSamples {
+    rest_sum: u32,
+    rest_min: u16,
+    rest_max: u16,
+    min: u16,
+    max: u16,
+}
+
+impl Default for AxisSamples {
+    fn default() -> Self {
+        Self {
+            rest_sum: 0,
+            rest_min: u16::MAX,
+            rest_max: 0,
+            min: u16::MAX,
+            max: 0,
+        }
+    }
+}
+
+impl AxisSamples {
+    fn rest(&mut self, raw: u16) {
+        self.rest_sum += raw as u32;
+        self.rest_min = self.rest_min.min(raw);
+        self.rest_max = self.rest_max.max(raw);
+        self.moved(raw);
+    }
+
+    fn moved(&mut self, raw: u16) {
+        self.min = self.min.min(raw);
+        self.max = self.max.max(raw);
+    }
+
+    fn finish(&self, rest_samples: u32) -> Option<Axis> {
+        let center = (self.rest_sum / rest_samples) as u16;
+        let span = (center - self.min).min(self.max - center);
+        if span < MIN_SPAN {
+            return None;
+        }
+        // Twice as far as it wandered at rest, at least a quarter of the span and at most half
+        let jitter = (center - self.rest_min).max(self.rest_max - center);
+        let deadzone = (2 * jitter).max(span / 4).min(span / 2);
+        Some(Axis {
+            center,
+            span,
+            deadzone,
+        })
+    }
+}
+
+/// Works out a [`Calibration`] from readings of the stick first left at rest, then pushed as far
+/// as it goes in every direction.
+#[derive(Debug, Default, Clone)]
+pub struct Calibrator {
+    x: AxisSamples,
+    y: AxisSamples,
+    rest_samples: u32,
+}
+
+impl Calibrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A raw reading while the stick is left alone.
+    pub fn sample_rest(&mut self, x: u16, y: u16) {
+        self.x.rest(x);
+        self.y.rest(y);
+        self.rest_samples += 1;
+    }
+
+    /// A raw reading while the stick is being pushed around its edges.
+    pub fn sample_moving(&mut self, x: u16, y: u16) {
+        self.x.moved(x);
+        self.y.moved(y);
+    }
+
+    /// `None` without any readings at rest, or if either axis was hardly moved.
+    pub fn finish(&self) -> Option<Calibration> {
+        if self.rest_samples == 0 {
+            return None;
+        }
+        Some(Calibration {
+            x: self.x.finish(self.rest_samples)?,
+            y: self.y.finish(self.rest_samples)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Axis, Calibration, Calibrator, FULL_DEFLECTION};
+    use crate::storage::test::FakeEeprom;
+
+    #[test]
+    fn deflection() {
+        let axis = Axis {
+            center: 520,
+            span: 480,
+            deadzone: 80,
+        };
+        assert_eq!(axis.deflection(520), 0);
+        assert_eq!(axis.deflection(600), 0);
+        assert_eq!(axis.deflection(440), 0);
+        assert_eq!(axis.deflection(601), 1);
+        assert_eq!(axis.deflection(800), FULL_DEFLECTION / 2);
+        assert_eq!(axis.deflection(240), -FULL_DEFLECTION / 2);
+        assert_eq!(axis.deflection(1000), FULL_DEFLECTION);
+        // Past the span on the longer side
+        assert_eq!(axis.deflection(1023), FULL_DEFLECTION);
+        assert_eq!(axis.deflection(0), -FULL_DEFLECTION);
+
+        // The old fixed thresholds
+        let default = Axis::default();
+        assert_eq!(default.deflection(750), 0);
+        assert!(default.deflection(751) > 0);
+        assert!(default.deflection(249) < 0);
+    }
+
+    #[test]
+    fn calibrate() {
+        let mut calibrator = Calibrator::new();
+        assert_eq!(calibrator.finish(), None);
+        // Resting off center, with a little noise on y
+        for i in 0..50 {
+            calibrator.sample_rest(530, 480 + i % 5);
+        }
+        // Not pushed yet
+        assert_eq!(calibrator.finish(), None);
+        for (x, y) in [(530, 0), (1023, 482), (40, 1000), (530, 900)] {
+            calibrator.sample_moving(x, y);
+        }
+
+        let calibration = calibrator.finish().unwrap();
+        assert_eq!(
+            calibration.x,
+            Axis {
+                center: 530,
+                span: 490,
+                deadzone: 122,
+            }
+        );
+        assert_eq!(calibration.y.center, 482);
+        assert_eq!(calibration.y.span, 482);
+        assert_eq!(calibration.y.deadzone, 120);
+
+        // A noisy stick gets a wider deadzone
+        let mut noisy = Calibrator::new();
+        for i in 0..20 {
+            noisy.sample_rest(500 + (i % 2) * 240, 500);
+        }
+        noisy.sample_moving(0, 0);
+        noisy.sample_moving(1023, 1023);
+        let noisy = noisy.finish().unwrap();
+        assert_eq!(noisy.x.center, 620);
+        assert_eq!(noisy.x.deadzone, noisy.x.span / 2);
+        assert_eq!(noisy.y.deadzone, 500 / 4);
+    }
+
+    #[test]
+    fn survives_power_cycle() {
+        let mut eeprom = FakeEeprom::new();
+        assert_eq!(Calibration::load(&mut eeprom, 100), None);
+
+        let calibration = Calibration {
+            x: Axis {
+                center: 510,
+                span: 470,
+                deadzone: 90,
+            },
+            y: Axis::default(),
+        };
+        calibration.save(&mut eeprom, 100);
+        assert!(eeprom.0[100 + Calibration::SIZE..]
+            .iter()
+            .all(|&byte| byte == 0xFF));
+        assert_eq!(Calibration::load(&mut eeprom, 100), Some(calibration));
+
+        for addr in 100..100 + Calibration::SIZE {
+            let mut corrupted = FakeEeprom(eeprom.0);
+            corrupted.0[addr] ^= 0x04;
+            assert_eq!(Calibration::load(&mut corrupted, 100), None, "{}", addr);
+        }
+    }
+}
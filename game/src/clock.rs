@@ -0,0 +1,146 @@
+//! Keeping time for the game loop. The board counts milliseconds in a timer interrupt, the tests
+//! use a clock they move by hand.
+
+/// Milliseconds since some point in the past, wrapping around after `u32::MAX`.
+pub trait Clock {
+    fn now_ms(&self) -> u32;
+}
+
+/// Fires every `interval_ms` against a [`Clock`]. Each tick is scheduled from when the last one
+/// was due rather than when it was noticed, so time spent between polls doesn't add up.
+#[derive(Debug, Clone)]
+pub struct Ticker {
+    interval_ms: u16,
+    next_ms: u32,
+}
+
+impl Ticker {
+    /// The first tick is a whole interval from now.
+    pub fn new<C: Clock>(clock: &C, interval_ms: u16) -> Self {
+        Self {
+            interval_ms,
+            next_ms: clock.now_ms().wrapping_add(interval_ms as u32),
+        }
+    }
+
+    pub fn interval_ms(&self) -> u16 {
+        self.interval_ms
+    }
+
+    /// The tick already scheduled still comes on time, the ones after it use `interval_ms`.
+    pub fn set_interval(&mut self, interval_ms: u16) {
+        self.interval_ms = interval_ms;
+    }
+
+    /// Schedules the next tick a whole interval from now, as if just created.
+    pub fn reset<C: Clock>(&mut self, clock: &C) {
+        self.next_ms = clock.now_ms().wrapping_add(self.interval_ms as u32);
+    }
+
+    /// Whether a tick has come due since the last one. When more than a whole interval late the
+    /// missed ticks are dropped, rather than firing them all at once to catch up.
+    pub fn poll<C: Clock>(&mut self, clock: &C) -> bool {
+        let now = clock.now_ms();
+        let late = now.wrapping_sub(self.next_ms);
+        // Not due yet, it is "negative" once wrapped
+        if late > u32::MAX / 2 {
+            return false;
+        }
+        self.next_ms = if late >= self.interval_ms as u32 {
+            now
+        } else {
+            self.next_ms
+        }
+        .wrapping_add(self.interval_ms as u32);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use super::{Clock, Ticker};
+
+    struct TestClock(Cell<u32>);
+
+    impl TestClock {
+        fn advance(&self, ms: u32) {
+            self.0.set(self.0.get().wrapping_add(ms));
+        }
+    }
+
+    impl Clock for TestClock {
+        fn now_ms(&self) -> u32 {
+            self.0.get()
+        }
+    }
+
+    /// The times between 0 and `until` the ticker fires, polled every `poll_ms`.
+    fn ticks(clock: &TestClock, ticker: &mut Ticker, poll_ms: u32, until: u32) -> Vec<u32> {
+        let start = clock.now_ms();
+        let mut fired = Vec::new();
+        while clock.now_ms().wrapping_sub(start) < until {
+            clock.advance(poll_ms);
+            if ticker.poll(clock) {
+                fired.push(clock.now_ms().wrapping_sub(start));
+            }
+        }
+        fired
+    }
+
+    #[test]
+    fn fixed_rate() {
+        let clock = TestClock(Cell::new(1000));
+        let mut ticker = Ticker::new(&clock, 100);
+        assert!(!ticker.poll(&clock));
+        assert_eq!(ticks(&clock, &mut ticker, 10, 400), [100, 200, 300, 400]);
+
+        // Polling late doesn't push the ticks after it back
+        clock.advance(130);
+        assert!(ticker.poll(&clock));
+        assert!(!ticker.poll(&clock));
+        clock.advance(70);
+        assert!(ticker.poll(&clock));
+
+        // Polled every 30ms the ticks land on the first poll after every 100ms
+        let mut ticker = Ticker::new(&clock, 100);
+        assert_eq!(ticks(&clock, &mut ticker, 30, 400), [120, 210, 300, 420]);
+    }
+
+    #[test]
+    fn falls_behind() {
+        let clock = TestClock(Cell::new(0));
+        let mut ticker = Ticker::new(&clock, 50);
+        clock.advance(320);
+        // One tick for all those missed, the next a whole interval later
+        assert!(ticker.poll(&clock));
+        assert!(!ticker.poll(&clock));
+        clock.advance(49);
+        assert!(!ticker.poll(&clock));
+        clock.advance(1);
+        assert!(ticker.poll(&clock));
+
+        ticker.reset(&clock);
+        clock.advance(49);
+        assert!(!ticker.poll(&clock));
+        clock.advance(1);
+        assert!(ticker.poll(&clock));
+    }
+
+    #[test]
+    fn change_interval() {
+        let clock = TestClock(Cell::new(0));
+        let mut ticker = Ticker::new(&clock, 100);
+        ticker.set_interval(60);
+        assert_eq!(ticker.interval_ms(), 60);
+        assert_eq!(ticks(&clock, &mut ticker, 10, 300), [100, 160, 220, 280]);
+    }
+
+    #[test]
+    fn wraps_around() {
+        let clock = TestClock(Cell::new(u32::MAX - 25));
+        let mut ticker = Ticker::new(&clock, 20);
+        assert_eq!(ticks(&clock, &mut ticker, 5, 100), [20, 40, 60, 80, 100]);
+    }
+}
@@ -1,9 +1,12 @@
 use ufmt::derive::uDebug;
-#[derive(Debug, uDebug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, uDebug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
     Up,
     Down,
     Left, 
+    // The default is only there for the `T: Default` bound of the `RingBuffer` in `TurnQueue`, it
+    // is never used as the starting heading of a snake
+    #[default]
     Right
 }
 
@@ -16,6 +19,13 @@ impl ufmt::uDisplay for Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
     pub fn get_opposite(&self) -> Self {
         match self {
             Direction::Up => Direction::Down,
@@ -0,0 +1,143 @@
+//! Seeding the `RandomGenerator` from noisy hardware readings so every power on plays a
+//! different game.
+
+/// Something noisy which can be sampled, e.g. a floating ADC channel or the joystick.
+pub trait EntropySource {
+    /// The next raw reading, only its low bits need to be unpredictable.
+    fn sample(&mut self) -> u16;
+}
+
+/// Mixes samples from any number of sources into a seed.
+///
+/// Every sample is folded into the state with a multiply, so a change in any bit of any sample
+/// ends up changing about half of the bits of [`Entropy::seed`].
+#[derive(Debug, Clone)]
+pub struct Entropy {
+    state: u64,
+    samples: u32,
+}
+
+impl Default for Entropy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Entropy {
+    pub fn new() -> Self {
+        Self {
+            state: 0x9E37_79B9_7F4A_7C15,
+            samples: 0,
+        }
+    }
+
+    /// Adds a single value, e.g. the time until the button was first pressed.
+    pub fn mix(&mut self, sample: u16) {
+        self.state =
+            (self.state.rotate_left(5) ^ sample as u64).wrapping_mul(0x517C_C1B7_2722_0A95);
+        self.samples = self.samples.wrapping_add(1);
+    }
+
+    /// Adds `count` samples taken from `source`.
+    pub fn gather<S: EntropySource>(&mut self, source: &mut S, count: usize) {
+        for _ in 0..count {
+            self.mix(source.sample());
+        }
+    }
+
+    /// The number of samples mixed in so far.
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// The seed for the samples mixed in so far.
+    pub fn seed(&self) -> u64 {
+        // The finaliser of SplitMix64
+        let mut z = self.state ^ self.samples as u64;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Entropy, EntropySource};
+
+    /// Plays back recorded readings, starting over when it runs out.
+    struct Canned<'a> {
+        samples: &'a [u16],
+        next: usize,
+    }
+
+    impl<'a> Canned<'a> {
+        fn new(samples: &'a [u16]) -> Self {
+            Self { samples, next: 0 }
+        }
+    }
+
+    impl EntropySource for Canned<'_> {
+        fn sample(&mut self) -> u16 {
+            let sample = self.samples[self.next % self.samples.len()];
+            self.next += 1;
+            sample
+        }
+    }
+
+    fn seed_of(samples: &[u16]) -> u64 {
+        let mut entropy = Entropy::new();
+        entropy.gather(&mut Canned::new(samples), samples.len());
+        entropy.seed()
+    }
+
+    // Readings of a floating A3 with the low bits jumping around
+    const FLOATING: [u16; 8] = [312, 309, 315, 310, 311, 308, 314, 312];
+
+    #[test]
+    fn same_samples_same_seed() {
+        assert_eq!(seed_of(&FLOATING), seed_of(&FLOATING));
+
+        let mut entropy = Entropy::new();
+        let mut source = Canned::new(&FLOATING);
+        entropy.gather(&mut source, 3);
+        entropy.gather(&mut source, 5);
+        assert_eq!(entropy.samples(), 8);
+        assert_eq!(entropy.seed(), seed_of(&FLOATING));
+    }
+
+    #[test]
+    fn order_and_count_matter() {
+        let mut reversed = FLOATING;
+        reversed.reverse();
+        assert_ne!(seed_of(&FLOATING), seed_of(&reversed));
+
+        // A stuck source still moves the seed with every sample
+        assert_ne!(seed_of(&[0; 4]), seed_of(&[0; 5]));
+        assert_ne!(seed_of(&[]), seed_of(&[0]));
+    }
+
+    #[test]
+    fn every_bit_changes_the_seed() {
+        let base = seed_of(&FLOATING);
+        for i in 0..FLOATING.len() {
+            for bit in 0..16 {
+                let mut samples = FLOATING;
+                samples[i] ^= 1 << bit;
+                let changed = (seed_of(&samples) ^ base).count_ones();
+                assert!(
+                    (12..=52).contains(&changed),
+                    "flipping bit {bit} of sample {i} changed {changed} bits"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn press_timing() {
+        let mut early = Entropy::new();
+        let mut late = early.clone();
+        early.mix(1021);
+        late.mix(1022);
+        assert_ne!(early.seed(), late.seed());
+    }
+}
@@ -0,0 +1,282 @@
+//! The best scores so far, saved to non-volatile memory (the EEPROM on the board) so they survive
+//! power cycles.
+//!
+//! The table is stored as a header, the records best first, and a checksum:
+//!
+//! | bytes | contents                                              |
+//! |-------|-------------------------------------------------------|
+//! | 1     | `MAGIC`                                               |
+//! | 1     | `VERSION`                                             |
+//! | 1     | number of records                                     |
+//! | 7     | each record: apples (u16), ticks (u32), mode, all LE  |
+//! | 2     | Fletcher-16 of everything before it                   |
+//!
+//! Anything which doesn't match, such as a blank EEPROM or one written by an older layout, loads
+//! as an empty table rather than garbage scores.
+
+use super::{
+    position::Boundary,
+    score::Score,
+    storage::{Fletcher16, Storage},
+    Mode,
+};
+
+const MAGIC: u8 = b'S';
+// Bump whenever the layout changes
+const VERSION: u8 = 1;
+const HEADER_BYTES: usize = 3;
+const RECORD_BYTES: usize = 7;
+const CHECKSUM_BYTES: usize = 2;
+
+const WALLS_BIT: u8 = 1 << 0;
+const LEVELS_BIT: u8 = 1 << 1;
+
+/// A score and the mode it was played in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub score: Score,
+    pub mode: Mode,
+}
+
+impl Entry {
+    fn encode(&self, buf: &mut [u8]) {
+        buf[..2].copy_from_slice(&self.score.apples.to_le_bytes());
+        buf[2..6].copy_from_slice(&self.score.ticks.to_le_bytes());
+        let mut mode = 0;
+        if self.mode.boundary == Boundary::Walls {
+            mode |= WALLS_BIT;
+        }
+        if self.mode.levels {
+            mode |= LEVELS_BIT;
+        }
+        buf[6] = mode;
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let mode = buf[6];
+        if mode & !(WALLS_BIT | LEVELS_BIT) != 0 {
+            return None;
+        }
+        let boundary = if mode & WALLS_BIT != 0 {
+            Boundary::Walls
+        } else {
+            Boundary::Wrap
+        };
+        Some(Self {
+            score: Score {
+                apples: u16::from_le_bytes([buf[0], buf[1]]),
+                ticks: u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]),
+            },
+            mode: Mode {
+                boundary,
+                levels: mode & LEVELS_BIT != 0,
+            },
+        })
+    }
+}
+
+/// The `N` best scores across every mode, best first.
+#[derive(Debug, Clone)]
+pub struct HighScores<const N: usize> {
+    entries: [Entry; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for HighScores<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> HighScores<N> {
+    /// The most bytes a table of `N` scores takes up in storage.
+    pub const SIZE: usize = HEADER_BYTES + N * RECORD_BYTES + CHECKSUM_BYTES;
+
+    /// An empty table.
+    pub fn new() -> Self {
+        Self {
+            entries: [Entry {
+                score: Score::default(),
+                mode: Mode::default(),
+            }; N],
+            len: 0,
+        }
+    }
+
+    /// Reads the table saved at `addr`, empty if there isn't a valid one there.
+    pub fn load<S: Storage>(storage: &mut S, addr: u16) -> Self {
+        let mut table = Self::new();
+        let mut header = [0; HEADER_BYTES];
+        storage.read(addr, &mut header);
+        let [magic, version, len] = header;
+        let len = len as usize;
+        if magic != MAGIC || version != VERSION || len > N {
+            return table;
+        }
+
+        let mut sum = Fletcher16::default();
+        sum.update(&header);
+        let mut record = [0; RECORD_BYTES];
+        for i in 0..len {
+            storage.read(addr + (HEADER_BYTES + i * RECORD_BYTES) as u16, &mut record);
+            sum.update(&record);
+            match Entry::decode(&record) {
+                Some(entry) => table.entries[i] = entry,
+                None => return Self::new(),
+            }
+        }
+        let mut checksum = [0; CHECKSUM_BYTES];
+        storage.read(
+            addr + (HEADER_BYTES + len * RECORD_BYTES) as u16,
+            &mut checksum,
+        );
+        if u16::from_le_bytes(checksum) != sum.value() {
+            return Self::new();
+        }
+        table.len = len;
+        table
+    }
+
+    /// Writes the table at `addr`, taking up at most `SIZE` bytes.
+    pub fn save<S: Storage>(&self, storage: &mut S, addr: u16) {
+        let header = [MAGIC, VERSION, self.len as u8];
+        let mut sum = Fletcher16::default();
+        sum.update(&header);
+        storage.write(addr, &header);
+        let mut record = [0; RECORD_BYTES];
+        for (i, entry) in self.iter().enumerate() {
+            entry.encode(&mut record);
+            sum.update(&record);
+            storage.write(addr + (HEADER_BYTES + i * RECORD_BYTES) as u16, &record);
+        }
+        storage.write(
+            addr + (HEADER_BYTES + self.len * RECORD_BYTES) as u16,
+            &sum.value().to_le_bytes(),
+        );
+    }
+
+    /// Adds `score` if it is good enough, returning its rank counted from 0. Games where no
+    /// apples were eaten never make the table, and a score equal to one already in it goes below.
+    pub fn insert(&mut self, score: Score, mode: Mode) -> Option<usize> {
+        if score.apples == 0 || N == 0 {
+            return None;
+        }
+        let rank = self.iter().take_while(|entry| entry.score >= score).count();
+        if rank >= N {
+            return None;
+        }
+        let last = self.len.min(N - 1);
+        self.entries.copy_within(rank..last, rank + 1);
+        self.entries[rank] = Entry { score, mode };
+        self.len = (self.len + 1).min(N);
+        Some(rank)
+    }
+
+    /// The best score played in `mode`.
+    pub fn best(&self, mode: Mode) -> Option<Score> {
+        self.iter()
+            .find(|entry| entry.mode == mode)
+            .map(|entry| entry.score)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries[..self.len].iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HighScores;
+    use crate::{position::Boundary, score::Score, storage::test::FakeEeprom, Mode};
+
+    fn score(apples: u16, ticks: u32) -> Score {
+        Score { apples, ticks }
+    }
+
+    const WALLS_LEVELS: Mode = Mode {
+        boundary: Boundary::Walls,
+        levels: true,
+    };
+
+    #[test]
+    fn ranking() {
+        let mut table = HighScores::<3>::new();
+        assert_eq!(table.insert(score(0, 10), Mode::default()), None);
+        assert_eq!(table.insert(score(4, 50), Mode::default()), Some(0));
+        assert_eq!(table.insert(score(6, 90), WALLS_LEVELS), Some(0));
+        // Ties go below the score already there
+        assert_eq!(table.insert(score(4, 50), WALLS_LEVELS), Some(2));
+        assert_eq!(table.insert(score(5, 10), Mode::default()), Some(1));
+        assert_eq!(table.insert(score(4, 60), Mode::default()), None);
+        let scores: Vec<_> = table.iter().map(|entry| entry.score).collect();
+        assert_eq!(scores, [score(6, 90), score(5, 10), score(4, 50)]);
+        assert_eq!(table.iter().last().unwrap().mode, Mode::default());
+        assert_eq!(table.best(WALLS_LEVELS), Some(score(6, 90)));
+        assert_eq!(table.best(Mode::default()), Some(score(5, 10)));
+        let wrap_levels = Mode {
+            boundary: Boundary::Wrap,
+            levels: true,
+        };
+        assert_eq!(table.best(wrap_levels), None);
+    }
+
+    #[test]
+    fn survives_power_cycle() {
+        let mut eeprom = FakeEeprom::new();
+        // Blank memory is an empty table
+        assert!(HighScores::<5>::load(&mut eeprom, 16).is_empty());
+
+        let mut table = HighScores::<5>::new();
+        table.insert(score(300, 70_000), WALLS_LEVELS);
+        table.insert(score(2, 9), Mode::default());
+        table.save(&mut eeprom, 16);
+        // Nothing outside of the table is touched
+        assert!(eeprom.0[..16].iter().all(|&byte| byte == 0xFF));
+        assert!(eeprom.0[16 + HighScores::<5>::SIZE..]
+            .iter()
+            .all(|&byte| byte == 0xFF));
+
+        let loaded = HighScores::<5>::load(&mut eeprom, 16);
+        assert!(loaded.iter().eq(table.iter()));
+        // A smaller table can't hold them all
+        assert!(HighScores::<1>::load(&mut eeprom, 16).is_empty());
+    }
+
+    #[test]
+    fn rejects_corruption() {
+        let mut eeprom = FakeEeprom::new();
+        let mut table = HighScores::<4>::new();
+        table.insert(score(7, 40), Mode::default());
+        table.insert(score(3, 20), WALLS_LEVELS);
+        table.save(&mut eeprom, 0);
+
+        for addr in 0..HighScores::<4>::SIZE - 2 * 7 {
+            let mut corrupted = FakeEeprom(eeprom.0);
+            corrupted.0[addr] ^= 0x10;
+            assert!(
+                HighScores::<4>::load(&mut corrupted, 0).is_empty(),
+                "{}",
+                addr
+            );
+        }
+
+        // An older layout
+        let mut old = FakeEeprom(eeprom.0);
+        old.0[1] = 0;
+        assert!(HighScores::<4>::load(&mut old, 0).is_empty());
+
+        assert_eq!(HighScores::<4>::load(&mut eeprom, 0).len(), 2);
+    }
+}
@@ -0,0 +1,140 @@
+//! The built-in levels: mazes of walls for an 8x8 matrix, kept in flash.
+
+use crate::{progmem, progmem::ProgMem};
+
+const ROWS: usize = 8;
+// The rows of walls followed by the target length
+const LEVEL_BYTES: usize = ROWS + 1;
+const COUNT: usize = 5;
+
+progmem! {
+    /// Every level is drawn top row first with the most significant bit on the left, so the bytes
+    /// look like the level does on the matrix.
+    static LEVELS: ProgMem<{ LEVEL_BYTES * COUNT }> = [
+        // Posts
+        0b0000_0000,
+        0b0100_0010,
+        0b0000_0000,
+        0b0000_0000,
+        0b0000_0000,
+        0b0000_0000,
+        0b0100_0010,
+        0b0000_0000,
+        8,
+        // Bars
+        0b0000_0000,
+        0b0000_0000,
+        0b0001_1000,
+        0b0000_0000,
+        0b0000_0000,
+        0b0001_1000,
+        0b0000_0000,
+        0b0000_0000,
+        10,
+        // Corners
+        0b1100_0011,
+        0b1000_0001,
+        0b0000_0000,
+        0b0000_0000,
+        0b0000_0000,
+        0b0000_0000,
+        0b1000_0001,
+        0b1100_0011,
+        12,
+        // Box, open in the middle of every side
+        0b1110_0111,
+        0b1000_0001,
+        0b1000_0001,
+        0b0000_0000,
+        0b0000_0000,
+        0b1000_0001,
+        0b1000_0001,
+        0b1110_0111,
+        14,
+        // Pillars
+        0b1000_0001,
+        0b0000_0000,
+        0b0010_0100,
+        0b0000_0000,
+        0b0000_0000,
+        0b0010_0100,
+        0b0000_0000,
+        0b1000_0001,
+        16,
+    ];
+}
+
+/// One of the built-in levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Level(u8);
+
+impl Level {
+    pub const COUNT: u8 = COUNT as u8;
+
+    pub fn first() -> Self {
+        Self(0)
+    }
+
+    /// The level at `index`, counted from 0, `None` past the last.
+    pub fn nth(index: u8) -> Option<Self> {
+        (index < Self::COUNT).then_some(Self(index))
+    }
+
+    /// The level after this one, `None` after the last.
+    pub fn next(self) -> Option<Self> {
+        if self.0 + 1 < Self::COUNT {
+            Some(Self(self.0 + 1))
+        } else {
+            None
+        }
+    }
+
+    /// Counted from 0.
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    /// The length the snake has to grow to for the level to be cleared.
+    pub fn target_len(self) -> u8 {
+        LEVELS.load(self.0 as usize * LEVEL_BYTES + ROWS)
+    }
+
+    /// Whether there is a wall at `x`, `y` (`y` counting up from the bottom like `Position`).
+    /// Boards larger than 8x8 repeat the level, once per matrix of the chain.
+    pub fn is_wall(self, x: usize, y: usize) -> bool {
+        let row = ROWS - 1 - y % ROWS;
+        let bits = LEVELS.load(self.0 as usize * LEVEL_BYTES + row);
+        bits & (0x80 >> (x % 8)) != 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Level;
+
+    #[test]
+    fn levels_in_order() {
+        let mut level = Level::first();
+        let mut count = 1;
+        while let Some(next) = level.next() {
+            assert_eq!(next.index(), level.index() + 1);
+            assert!(next.target_len() > level.target_len());
+            level = next;
+            count += 1;
+        }
+        assert_eq!(count, Level::COUNT);
+    }
+
+    #[test]
+    fn bitmap_layout() {
+        let corners = Level(2);
+        // Top left corner of the bitmap is the top left of the board
+        assert!(corners.is_wall(0, 7) && corners.is_wall(1, 7) && corners.is_wall(0, 6));
+        assert!(!corners.is_wall(1, 6));
+        assert!(corners.is_wall(7, 0) && corners.is_wall(6, 0) && corners.is_wall(7, 1));
+        assert!(!corners.is_wall(3, 3));
+        // Repeated on a chain of matrices
+        assert!(corners.is_wall(8, 7) && corners.is_wall(15, 0));
+        assert!(!corners.is_wall(9, 6));
+    }
+}
@@ -0,0 +1,759 @@
+#![cfg_attr(not(test), no_std)]
+#![cfg_attr(target_arch = "avr", feature(asm_experimental_arch))]
+
+pub mod board;
+pub mod button;
+pub mod calibration;
+pub mod clock;
+pub mod direction;
+pub mod entropy;
+pub mod highscores;
+pub mod level;
+pub mod occupancy;
+pub mod position;
+pub mod progmem;
+pub mod rand;
+pub mod replay;
+pub mod resolver;
+pub mod ringbuffer;
+pub mod score;
+pub mod snake;
+pub mod speed;
+pub mod storage;
+#[cfg(test)]
+mod test_util;
+pub mod turns;
+
+use ufmt::{uDisplay, uwrite, uwriteln};
+
+use crate::rand::RandomGenerator;
+
+use self::{
+    board::Board,
+    direction::Direction,
+    level::Level,
+    position::{Boundary, Position},
+    score::Score,
+    snake::Snake,
+    speed::Speed,
+};
+
+/// The snake capacity and board size of a single 8x8 matrix, used when `Game` is not given any.
+pub const DEFAULT_SNAKE_LEN: usize = 20;
+pub const DEFAULT_X_LIM: isize = 8;
+pub const DEFAULT_Y_LIM: isize = 8;
+
+const START_LEN: u8 = 3;
+// Free cells around the head of a new snake with walls, so it never spawns right next to one
+const SPAWN_CLEARANCE: isize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Reset,
+    Move(Direction),
+    /// Stops the snake where it is, moves are ignored until `Resume`.
+    Pause,
+    Resume,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    Continue,
+    Died,
+    Won,
+    Restarting,
+    NextLevel,
+    /// The game is paused, nothing moved.
+    Paused,
+}
+
+/// How a game is played, picked when it is started.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Mode {
+    pub boundary: Boundary,
+    /// Play through the built-in levels, each cleared by growing the snake to its target length,
+    /// rather than on an empty board until it is full.
+    pub levels: bool,
+}
+
+pub struct Game<
+    const SNAKE_LEN: usize = DEFAULT_SNAKE_LEN,
+    const X_LIM: isize = DEFAULT_X_LIM,
+    const Y_LIM: isize = DEFAULT_Y_LIM,
+> {
+    snake: Snake<SNAKE_LEN, X_LIM, Y_LIM>,
+    apple: Position<X_LIM, Y_LIM>,
+    rand_gen: RandomGenerator,
+    board: Board<X_LIM, Y_LIM>,
+    // `None` unless playing through the levels
+    level: Option<Level>,
+    // Since the game started or the snake last died
+    score: Score,
+    // The score when the snake last died, the game was won or reset
+    last_score: Score,
+    best_score: Score,
+    speed: Speed,
+    paused: bool,
+    // The state of `rand_gen` just before the current snake was spawned
+    start_seed: u64,
+}
+
+impl<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize> Game<SNAKE_LEN, X_LIM, Y_LIM> {
+    // A new snake has to fit in a straight line whichever way it faces, even with walls
+    const FITS_SNAKE: bool = {
+        assert!(
+            X_LIM >= START_LEN as isize && Y_LIM >= START_LEN as isize,
+            "board is too small for a new snake"
+        );
+        true
+    };
+
+    /// A game on an empty board where the snake wraps around the edges.
+    pub fn new(rand_gen: RandomGenerator) -> Self {
+        Self::with_mode(rand_gen, Mode::default())
+    }
+
+    pub fn with_mode(rand_gen: RandomGenerator, mode: Mode) -> Self {
+        Self::at_level(rand_gen, mode.boundary, mode.levels.then(Level::first))
+    }
+
+    /// A game starting on `level`, or on an empty board when it is `None`.
+    pub fn at_level(
+        mut rand_gen: RandomGenerator,
+        boundary: Boundary,
+        level: Option<Level>,
+    ) -> Self {
+        // Referenced so boards which are too small fail to compile
+        let _ = Self::FITS_SNAKE;
+        let mut board = Board::new(boundary);
+        board.load(level);
+        let start_seed = rand_gen.state();
+        let snake = spawn_snake(&mut rand_gen, &board);
+        let apple = random_free_cell(&snake, &board, &mut rand_gen).unwrap_or_default();
+        Self {
+            snake,
+            apple,
+            rand_gen,
+            board,
+            level,
+            score: Score::default(),
+            last_score: Score::default(),
+            best_score: Score::default(),
+            speed: Speed::default(),
+            paused: false,
+            start_seed,
+        }
+    }
+
+    pub fn step(&mut self, cmd: Command) -> GameResult {
+        match cmd {
+            Command::Reset => {
+                self.reset();
+                GameResult::Restarting
+            }
+            Command::Pause => {
+                self.paused = true;
+                GameResult::Paused
+            }
+            Command::Resume => {
+                self.paused = false;
+                GameResult::Continue
+            }
+            // Paused steps don't count towards the score either
+            Command::Move(_) if self.paused => GameResult::Paused,
+            Command::Move(dir) => {
+                self.score.ticks = self.score.ticks.saturating_add(1);
+                let result = self.snake.move_dir(dir, self.apple, &self.board);
+                self.moved(result)
+            }
+        }
+    }
+
+    fn moved(&mut self, result: snake::MovementResult) -> GameResult {
+        let ate = matches!(
+            result,
+            snake::MovementResult::AteApple | snake::MovementResult::AteAppleAndMaxed
+        );
+        if ate {
+            self.score.apples = self.score.apples.saturating_add(1);
+        }
+        match result {
+            snake::MovementResult::BitSelf | snake::MovementResult::HitWall => {
+                self.end_score();
+                self.restart();
+                GameResult::Died
+            }
+            snake::MovementResult::Moving => GameResult::Continue,
+            snake::MovementResult::AteApple if self.level_cleared() => self.next_level(),
+            snake::MovementResult::AteApple => {
+                match random_free_cell(&self.snake, &self.board, &mut self.rand_gen) {
+                    Some(apple) => {
+                        self.apple = apple;
+                        GameResult::Continue
+                    }
+                    // The snake covers the whole board, there is nowhere left to go
+                    None if self.level.is_some() => self.next_level(),
+                    None => {
+                        self.reset();
+                        GameResult::Won
+                    }
+                }
+            }
+            snake::MovementResult::AteAppleAndMaxed if self.level.is_some() => self.next_level(),
+            snake::MovementResult::AteAppleAndMaxed => {
+                self.reset();
+                GameResult::Won
+            }
+        }
+    }
+
+    /// Starts over, from the first level when playing through the levels.
+    pub fn reset(&mut self) {
+        self.paused = false;
+        self.end_score();
+        if self.level.is_some() {
+            self.level = Some(Level::first());
+            self.board.load(self.level);
+        }
+        self.restart();
+    }
+
+    fn end_score(&mut self) {
+        self.last_score = self.score;
+        self.best_score = self.best_score.max(self.score);
+        self.score = Score::default();
+    }
+
+    // A new snake and apple on the current level
+    fn restart(&mut self) {
+        self.start_seed = self.rand_gen.state();
+        self.snake = spawn_snake(&mut self.rand_gen, &self.board);
+        self.apple =
+            random_free_cell(&self.snake, &self.board, &mut self.rand_gen).unwrap_or_default();
+    }
+
+    fn level_cleared(&self) -> bool {
+        match self.level {
+            Some(level) => self.snake.len() >= level.target_len() as usize,
+            None => false,
+        }
+    }
+
+    // Moves on to the level after the current one, the game is won after the last level
+    fn next_level(&mut self) -> GameResult {
+        match self.level.and_then(Level::next) {
+            Some(next) => {
+                self.level = Some(next);
+                self.board.load(self.level);
+                self.restart();
+                GameResult::NextLevel
+            }
+            None => {
+                self.reset();
+                GameResult::Won
+            }
+        }
+    }
+
+    pub fn iter_snake(&self) -> ringbuffer::RingBufferIter<Position<X_LIM, Y_LIM>, SNAKE_LEN> {
+        self.snake.iter()
+    }
+
+    /// The way the snake last moved.
+    pub fn heading(&self) -> Direction {
+        self.snake.current_dir()
+    }
+
+    pub fn apple(&self) -> Position<X_LIM, Y_LIM> {
+        self.apple
+    }
+
+    pub fn board(&self) -> &Board<X_LIM, Y_LIM> {
+        &self.board
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.board.boundary()
+    }
+
+    /// The current level, `None` unless playing through the levels.
+    pub fn level(&self) -> Option<Level> {
+        self.level
+    }
+
+    /// The score since the game started or the snake last died.
+    pub fn score(&self) -> Score {
+        self.score
+    }
+
+    /// The final score of the last game, set when the snake dies, the game is won or reset.
+    pub fn last_score(&self) -> Score {
+        self.last_score
+    }
+
+    /// The best final score since the game was created.
+    pub fn best_score(&self) -> Score {
+        self.best_score
+    }
+
+    /// Whether the game was paused by `Command::Pause` and not resumed since.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The seed the current snake was spawned from. `at_level` with it and the current level
+    /// starts a game which plays out the same as this one has since.
+    pub fn start_seed(&self) -> u64 {
+        self.start_seed
+    }
+
+    pub fn speed(&self) -> Speed {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: Speed) {
+        self.speed = speed;
+    }
+
+    /// How long to wait before the next step.
+    pub fn tick_interval_ms(&self) -> u16 {
+        self.speed.interval_ms(self.score.apples)
+    }
+
+    pub fn mode(&self) -> Mode {
+        Mode {
+            boundary: self.board.boundary(),
+            levels: self.level.is_some(),
+        }
+    }
+
+    pub fn last_snake_tail(&self) -> &Position<X_LIM, Y_LIM> {
+        self.snake.snake_tail()
+    }
+}
+
+/// A new snake at a random position, picked from every place it fits with equal probability.
+/// It is placed so its body is clear of walls and the edges when they are walls, with its head
+/// `SPAWN_CLEARANCE` cells away from any, whichever way it is first steered. Where there is no
+/// such place the clearance is given up a cell at a time.
+fn spawn_snake<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    rand_gen: &mut RandomGenerator,
+    board: &Board<X_LIM, Y_LIM>,
+) -> Snake<SNAKE_LEN, X_LIM, Y_LIM> {
+    for clearance in (0..=SPAWN_CLEARANCE).rev() {
+        let placements = || {
+            (0..Y_LIM)
+                .flat_map(|y| (0..X_LIM).map(move |x| Position::new(x as u8, y as u8)))
+                .flat_map(|start| Direction::ALL.into_iter().map(move |dir| (start, dir)))
+                .filter(move |(start, dir)| fits_snake(board, start, *dir, clearance))
+        };
+        let count = placements().count();
+        if count > 0 {
+            let (start, dir) = placements()
+                .nth(rand_gen.generate_range(0, count))
+                .unwrap_or_default();
+            return Snake::new(start, START_LEN, dir);
+        }
+    }
+    // `FITS_SNAKE` leaves room on an empty board, and every level is drawn with room to spare
+    unreachable!("no room for a new snake")
+}
+
+// Whether a new snake from `start` heading `dir` is clear of walls, with its head `clearance`
+// cells away from any
+fn fits_snake<const X_LIM: isize, const Y_LIM: isize>(
+    board: &Board<X_LIM, Y_LIM>,
+    start: &Position<X_LIM, Y_LIM>,
+    dir: Direction,
+    clearance: isize,
+) -> bool {
+    if !board.is_clear(start, dir, START_LEN as isize) {
+        return false;
+    }
+    let head = start.offset_dir_scaled(dir, START_LEN as isize - 1);
+    Direction::ALL
+        .iter()
+        .all(|&dir| board.is_clear(&head, dir, clearance + 1))
+}
+
+/// Picks one of the cells not covered by the snake or a wall with equal probability, `None` if
+/// the snake fills the whole board.
+fn random_free_cell<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    snake: &Snake<SNAKE_LEN, X_LIM, Y_LIM>,
+    board: &Board<X_LIM, Y_LIM>,
+    rand_gen: &mut RandomGenerator,
+) -> Option<Position<X_LIM, Y_LIM>> {
+    let free_cells = || snake.free_cells().filter(|pos| !board.is_wall(pos));
+    let free = free_cells().count();
+    if free == 0 {
+        return None;
+    }
+    let idx = rand_gen.generate_range(0, free);
+    free_cells().nth(idx)
+}
+
+impl<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize> uDisplay
+    for Game<SNAKE_LEN, X_LIM, Y_LIM>
+{
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        for y in (0..Y_LIM).rev() {
+            for x in 0..X_LIM {
+                let pos = Position::new(x as u8, y as u8);
+                let cell = if self.snake.check_overlap(&pos) {
+                    'S'
+                } else if self.board.is_wall(&pos) {
+                    '#'
+                } else if pos == self.apple {
+                    'A'
+                } else {
+                    '-'
+                };
+                uwrite!(f, "{} ", cell)?;
+            }
+            uwriteln!(f, "")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{random_free_cell, Command, Game, GameResult, Mode, SPAWN_CLEARANCE, START_LEN};
+    use crate::{
+        board::Board,
+        direction::Direction,
+        level::Level,
+        position::{Boundary, Position},
+        rand::RandomGenerator,
+        score::Score,
+        snake::Snake,
+        speed::Speed,
+        test_util::render,
+    };
+
+    const WALLS: Mode = Mode {
+        boundary: Boundary::Walls,
+        levels: false,
+    };
+
+    fn towards_apple<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+        game: &Game<SNAKE_LEN, X_LIM, Y_LIM>,
+    ) -> Direction {
+        let head = game.iter_snake().last().unwrap();
+        let apple = game.apple();
+        if head.x() < apple.x() {
+            Direction::Right
+        } else if head.x() > apple.x() {
+            Direction::Left
+        } else if head.y() < apple.y() {
+            Direction::Up
+        } else {
+            Direction::Down
+        }
+    }
+
+    fn check_apple_never_on_snake<
+        const SNAKE_LEN: usize,
+        const X_LIM: isize,
+        const Y_LIM: isize,
+    >() {
+        let mut game = Game::<SNAKE_LEN, X_LIM, Y_LIM>::new(RandomGenerator::new(0xDEADBEEF));
+        let mut eaten = 0;
+        for _ in 0..10_000 {
+            assert!(!game.snake.check_overlap(&game.apple()));
+            let len = game.iter_snake().count();
+            game.step(Command::Move(towards_apple(&game)));
+            if game.iter_snake().count() > len {
+                eaten += 1;
+            }
+        }
+        assert!(eaten > 100);
+    }
+
+    #[test]
+    fn apple_never_on_snake() {
+        check_apple_never_on_snake::<20, 8, 8>();
+        check_apple_never_on_snake::<40, 16, 8>();
+        check_apple_never_on_snake::<80, 32, 8>();
+    }
+
+    // The direction from the cell behind the head to the head, without wrapping
+    fn heading<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+        game: &Game<SNAKE_LEN, X_LIM, Y_LIM>,
+    ) -> Option<Direction> {
+        let len = game.iter_snake().count();
+        let mut body = game.iter_snake().skip(len.checked_sub(2)?);
+        let (neck, head) = (body.next()?, body.next()?);
+        Direction::ALL
+            .into_iter()
+            .find(|&dir| neck.offset_dir_bounded(dir, 1, Boundary::Walls) == Some(*head))
+    }
+
+    #[test]
+    fn walls_spawn_on_board() {
+        for seed in 0..200 {
+            let game = Game::<20, 8, 8>::with_mode(RandomGenerator::new(seed), WALLS);
+            assert!(heading(&game).is_some(), "body wraps around an edge");
+            let head = game.iter_snake().last().unwrap();
+            for dir in Direction::ALL {
+                assert!(head
+                    .offset_dir_bounded(dir, SPAWN_CLEARANCE, Boundary::Walls)
+                    .is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn small_walls_spawn() {
+        // Too small for the full clearance, the snake still has to fit
+        for seed in 0..20 {
+            let game = Game::<4, 4, 4>::with_mode(RandomGenerator::new(seed), WALLS);
+            assert!(heading(&game).is_some(), "body wraps around an edge");
+            let game = Game::<4, 3, 5>::with_mode(RandomGenerator::new(seed), WALLS);
+            assert!(heading(&game).is_some(), "body wraps around an edge");
+        }
+
+        // The smallest board there is, a snake fits along every row and column either way
+        let mut spawns = std::collections::HashSet::new();
+        for seed in 0..200 {
+            let game = Game::<3, 3, 3>::with_mode(RandomGenerator::new(seed), WALLS);
+            assert!(heading(&game).is_some(), "body wraps around an edge");
+            spawns.insert(
+                game.iter_snake()
+                    .map(|pos| (pos.x(), pos.y()))
+                    .collect::<Vec<_>>(),
+            );
+        }
+        assert_eq!(spawns.len(), 12);
+    }
+
+    #[test]
+    fn walls_kill() {
+        let mut game = Game::<20, 8, 8>::with_mode(RandomGenerator::new(7), WALLS);
+        assert_eq!(game.boundary(), Boundary::Walls);
+        let dir = heading(&game).unwrap();
+        let mut steps = 0;
+        let result = loop {
+            match game.step(Command::Move(dir)) {
+                GameResult::Continue => steps += 1,
+                result => break result,
+            }
+        };
+        assert_eq!(result, GameResult::Died);
+        assert!((SPAWN_CLEARANCE..8).contains(&steps));
+        // Reset with walls as well
+        assert!(heading(&game).is_some());
+
+        let mut game = Game::<20, 8, 8>::new(RandomGenerator::new(7));
+        assert_eq!(game.boundary(), Boundary::Wrap);
+        for _ in 0..16 {
+            assert_eq!(game.step(Command::Move(dir)), GameResult::Continue);
+        }
+    }
+
+    #[test]
+    fn levels_spawn_clear_of_walls() {
+        for boundary in [Boundary::Wrap, Boundary::Walls] {
+            let mode = Mode {
+                boundary,
+                levels: true,
+            };
+            let mut game = Game::<20, 8, 8>::with_mode(RandomGenerator::new(0xDEADBEEF), mode);
+            let mut level = Some(Level::first());
+            while let Some(current) = level {
+                game.level = level;
+                game.board.load(level);
+                for _ in 0..100 {
+                    game.restart();
+                    assert!(game.iter_snake().all(|pos| !game.board.is_wall(pos)));
+                    assert!(!game.board.is_wall(&game.apple()));
+                    let head = game.iter_snake().last().unwrap();
+                    for dir in Direction::ALL {
+                        assert!(game.board.is_clear(head, dir, SPAWN_CLEARANCE + 1));
+                    }
+                }
+                level = current.next();
+            }
+        }
+    }
+
+    #[test]
+    fn levels_advance() {
+        let mode = Mode {
+            boundary: Boundary::Wrap,
+            levels: true,
+        };
+        let mut game = Game::<20, 8, 8>::with_mode(RandomGenerator::new(0xDEADBEEF), mode);
+        assert_eq!(game.level(), Some(Level::first()));
+        assert_eq!(game.mode(), mode);
+        let mut cleared = 0;
+        for _ in 0..20_000 {
+            let level = game.level().unwrap();
+            let len = game.iter_snake().count();
+            let result = game.step(Command::Move(towards_apple(&game)));
+            assert!(!game.snake.check_overlap(&game.apple()));
+            assert!(!game.board.is_wall(&game.apple()));
+            match result {
+                GameResult::NextLevel => {
+                    assert_eq!(len + 1, level.target_len() as usize);
+                    assert_eq!(game.level(), level.next());
+                    assert_eq!(game.iter_snake().count(), START_LEN as usize);
+                    cleared += 1;
+                }
+                GameResult::Won => {
+                    assert_eq!(level.next(), None);
+                    assert_eq!(game.level(), Some(Level::first()));
+                }
+                _ => assert_eq!(game.level(), Some(level)),
+            }
+        }
+        assert!(cleared > 0);
+
+        game.step(Command::Reset);
+        assert_eq!(game.level(), Some(Level::first()));
+    }
+
+    #[test]
+    fn speeds_up_with_apples() {
+        let mut game = Game::<20, 8, 8>::new(RandomGenerator::new(0xDEADBEEF));
+        assert_eq!(game.tick_interval_ms(), Speed::default().start_ms);
+        let mut fastest = u16::MAX;
+        for _ in 0..2000 {
+            let apples = game.score().apples;
+            let len = game.iter_snake().count();
+            match game.step(Command::Move(towards_apple(&game))) {
+                GameResult::Continue if game.iter_snake().count() > len => {
+                    assert_eq!(game.score().apples, apples + 1)
+                }
+                GameResult::Continue => assert_eq!(game.score().apples, apples),
+                _ => assert_eq!(game.score().apples, 0),
+            }
+            assert_eq!(
+                game.tick_interval_ms(),
+                Speed::default().interval_ms(game.score().apples)
+            );
+            fastest = fastest.min(game.tick_interval_ms());
+        }
+        assert_eq!(fastest, Speed::default().floor_ms);
+
+        game.set_speed(Speed::constant(150));
+        assert_eq!(game.speed(), Speed::constant(150));
+        assert_eq!(game.tick_interval_ms(), 150);
+    }
+
+    #[test]
+    fn scores() {
+        let mut game = Game::<20, 8, 8>::new(RandomGenerator::new(0xDEADBEEF));
+        let mut best = Score::default();
+        let mut games = 0;
+        for _ in 0..5000 {
+            let before = game.score();
+            match game.step(Command::Move(towards_apple(&game))) {
+                GameResult::Continue => {
+                    assert_eq!(game.score().ticks, before.ticks + 1);
+                    assert!(game.score().apples - before.apples <= 1);
+                }
+                GameResult::Died | GameResult::Won => {
+                    let last = game.last_score();
+                    assert_eq!(last.ticks, before.ticks + 1);
+                    assert!(last.apples - before.apples <= 1);
+                    assert_eq!(game.score(), Score::default());
+                    best = best.max(last);
+                    games += 1;
+                }
+                result => panic!("unexpected {result:?}"),
+            }
+            assert_eq!(game.best_score(), best);
+        }
+        assert!(games > 1 && best.apples > 0);
+
+        // A reset ends the game as well
+        game.step(Command::Move(towards_apple(&game)));
+        let before = game.score();
+        assert_eq!(game.step(Command::Reset), GameResult::Restarting);
+        assert_eq!(game.last_score(), before);
+        assert_eq!(game.score(), Score::default());
+    }
+
+    #[test]
+    fn pause() {
+        let mut game = Game::<20, 8, 8>::new(RandomGenerator::new(0xDEADBEEF));
+        game.step(Command::Move(towards_apple(&game)));
+        let snake: Vec<_> = game.iter_snake().copied().collect();
+        let score = game.score();
+
+        assert_eq!(game.step(Command::Pause), GameResult::Paused);
+        assert!(game.is_paused());
+        for dir in Direction::ALL {
+            assert_eq!(game.step(Command::Move(dir)), GameResult::Paused);
+        }
+        assert_eq!(game.iter_snake().copied().collect::<Vec<_>>(), snake);
+        assert_eq!(game.score(), score);
+
+        // Carries on from where it stopped
+        assert_eq!(game.step(Command::Resume), GameResult::Continue);
+        assert!(!game.is_paused());
+        assert_eq!(
+            game.step(Command::Move(game.heading())),
+            GameResult::Continue
+        );
+        assert_eq!(game.score().ticks, score.ticks + 1);
+
+        // A reset while paused starts a new game straight away
+        game.step(Command::Pause);
+        assert_eq!(game.step(Command::Reset), GameResult::Restarting);
+        assert!(!game.is_paused());
+    }
+
+    #[test]
+    fn display_follows_board_size() {
+        let game = Game::<40, 16, 8>::new(RandomGenerator::new(0xDEADBEEF));
+        let screen = render(&game);
+
+        assert_eq!(screen.lines().count(), 8);
+        for line in screen.lines() {
+            assert_eq!(line.split_whitespace().count(), 16);
+        }
+        let cells = screen.split_whitespace();
+        assert_eq!(cells.clone().filter(|&cell| cell == "S").count(), 3);
+        assert_eq!(cells.filter(|&cell| cell == "A").count(), 1);
+    }
+
+    #[test]
+    fn last_free_cell() {
+        let snake = Snake::<4, 1, 4>::new(Position::new(0, 0), 3, Direction::Up);
+        let board = Board::new(Boundary::Wrap);
+        for seed in 0..100 {
+            let mut rand_gen = RandomGenerator::new(seed);
+            assert_eq!(
+                random_free_cell(&snake, &board, &mut rand_gen),
+                Some(Position::new(0, 3))
+            );
+        }
+    }
+
+    #[test]
+    fn full_board() {
+        let snake = Snake::<4, 1, 4>::new(Position::new(0, 0), 4, Direction::Up);
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        let board = Board::new(Boundary::Wrap);
+        assert_eq!(random_free_cell(&snake, &board, &mut rand_gen), None);
+    }
+
+    #[test]
+    fn every_free_cell_reachable() {
+        let snake = Snake::<4, 1, 4>::new(Position::new(0, 0), 1, Direction::Up);
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        let board = Board::new(Boundary::Wrap);
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let apple = random_free_cell(&snake, &board, &mut rand_gen).unwrap();
+            seen[apple.y() as usize] = true;
+        }
+        assert_eq!(seen, [false, true, true, true]);
+    }
+}
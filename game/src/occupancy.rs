@@ -0,0 +1,116 @@
+use super::position::Position;
+
+/// The largest board, in cells, an [`Occupancy`] can track.
+pub const MAX_CELLS: usize = 256;
+
+/// A bitmap with one bit per cell of the board.
+///
+/// The storage is sized for [`MAX_CELLS`] since the array length can not be derived from the
+/// board dimensions; bytes are used rather than wider words as the AVR is an 8 bit core.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occupancy<const X_LIM: isize, const Y_LIM: isize> {
+    bits: [u8; MAX_CELLS / 8],
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> Default for Occupancy<X_LIM, Y_LIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> Occupancy<X_LIM, Y_LIM> {
+    const CELLS: usize = {
+        assert!(
+            X_LIM > 0 && Y_LIM > 0 && X_LIM * Y_LIM <= MAX_CELLS as isize,
+            "board does not fit in the occupancy bitmap"
+        );
+        (X_LIM * Y_LIM) as usize
+    };
+
+    pub fn new() -> Self {
+        // Referenced so boards which are too large fail to compile
+        let _ = Self::CELLS;
+        Self {
+            bits: [0; MAX_CELLS / 8],
+        }
+    }
+
+    #[inline]
+    fn idx(pos: &Position<X_LIM, Y_LIM>) -> (usize, u8) {
+        let idx = pos.y() as usize * X_LIM as usize + pos.x() as usize;
+        (idx >> 3, 1 << (idx & 0x07))
+    }
+
+    #[inline]
+    pub fn is_set(&self, pos: &Position<X_LIM, Y_LIM>) -> bool {
+        let (byte, mask) = Self::idx(pos);
+        self.bits[byte] & mask != 0
+    }
+
+    #[inline]
+    pub fn set(&mut self, pos: &Position<X_LIM, Y_LIM>, state: bool) {
+        let (byte, mask) = Self::idx(pos);
+        if state {
+            self.bits[byte] |= mask;
+        } else {
+            self.bits[byte] &= !mask;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bits = [0; MAX_CELLS / 8];
+    }
+
+    /// The number of cells set.
+    pub fn count(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Occupancy;
+    use crate::position::Position;
+
+    #[test]
+    fn set_and_clear() {
+        let mut occupancy = Occupancy::<8, 8>::new();
+        let corners = [
+            Position::new(0, 0),
+            Position::new(7, 0),
+            Position::new(0, 7),
+            Position::new(7, 7),
+        ];
+        for pos in corners.iter() {
+            assert!(!occupancy.is_set(pos));
+            occupancy.set(pos, true);
+            assert!(occupancy.is_set(pos));
+        }
+        assert_eq!(occupancy.count(), 4);
+        assert!(!occupancy.is_set(&Position::new(1, 0)));
+        assert!(!occupancy.is_set(&Position::new(0, 1)));
+
+        occupancy.set(&corners[0], false);
+        assert!(!occupancy.is_set(&corners[0]));
+        assert_eq!(occupancy.count(), 3);
+
+        occupancy.clear();
+        assert_eq!(occupancy.count(), 0);
+    }
+
+    #[test]
+    fn every_cell_is_distinct() {
+        let mut occupancy = Occupancy::<32, 8>::new();
+        for y in 0..8 {
+            for x in 0..32 {
+                let pos = Position::new(x, y);
+                assert!(!occupancy.is_set(&pos));
+                occupancy.set(&pos, true);
+            }
+        }
+        assert_eq!(occupancy.count(), 256);
+    }
+}
@@ -0,0 +1,143 @@
+use super::direction::Direction;
+
+/// What happens at the edges of the board.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// Moving off one edge comes back in on the opposite edge.
+    #[default]
+    Wrap,
+    /// The edges are walls.
+    Walls,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position<const X_LIM: isize, const Y_LIM: isize> {
+    x: u8,
+    y: u8,
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> ufmt::uDebug for Position<X_LIM, Y_LIM> {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized {
+        ufmt::uwrite!(f, "(x: {}, y: {})", self.x, self.y)
+    }
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> ufmt::uDisplay for Position<X_LIM, Y_LIM> {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized {
+        ufmt::uwrite!(f, "(x: {}, y: {})", self.x, self.y)
+    }
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> Position<X_LIM, Y_LIM> {
+    pub fn new(x: u8, y: u8) -> Self {
+        Self { x, y }
+    }
+    pub fn offset(&self, x: isize, y: isize) -> Self {
+        let x = x + (self.x as isize);
+        let x = if x >= X_LIM {
+            x % X_LIM
+        } else if x < 0 {
+            X_LIM + x
+        } else {
+            x
+        };
+        let y = y + (self.y as isize);
+        let y = if y >= Y_LIM {
+            y % Y_LIM
+        } else if y < 0 {
+            Y_LIM + y
+        } else {
+            y
+        };
+        Self {
+            x: x as u8,
+            y: y as u8,
+        }
+    }
+
+    pub fn offset_dir(&self, dir: Direction) -> Self {
+        self.offset_dir_scaled(dir, 1)
+    }
+
+    /// The cell `scale` steps in `dir`, `None` if that is off the board and the edges are walls.
+    pub fn offset_dir_bounded(
+        &self,
+        dir: Direction,
+        scale: isize,
+        boundary: Boundary,
+    ) -> Option<Self> {
+        let (x, y) = match dir {
+            Direction::Up => (0, scale),
+            Direction::Down => (0, -scale),
+            Direction::Left => (-scale, 0),
+            Direction::Right => (scale, 0),
+        };
+        let (new_x, new_y) = (self.x as isize + x, self.y as isize + y);
+        let on_board = (0..X_LIM).contains(&new_x) && (0..Y_LIM).contains(&new_y);
+        if boundary == Boundary::Walls && !on_board {
+            None
+        } else {
+            Some(self.offset(x, y))
+        }
+    }
+
+    pub fn offset_dir_scaled(&self, dir: Direction, scale: isize) -> Self {
+        match dir {
+            Direction::Up => self.offset(0, scale),
+            Direction::Down => self.offset(0, -scale),
+            Direction::Left => self.offset(-scale, 0),
+            Direction::Right => self.offset(scale, 0),
+        }
+    }
+
+    pub fn x(&self) -> u8 {
+        self.x
+    }
+
+    pub fn y(&self) -> u8 {
+        self.y
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Boundary, Position};
+    use crate::direction::Direction;
+
+    #[test]
+    fn bounded_offsets() {
+        let corner = Position::<8, 8>::new(7, 0);
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Right, 1, Boundary::Wrap),
+            Some(Position::new(0, 0))
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Down, 1, Boundary::Wrap),
+            Some(Position::new(7, 7))
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Right, 1, Boundary::Walls),
+            None
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Down, 1, Boundary::Walls),
+            None
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Left, 7, Boundary::Walls),
+            Some(Position::new(0, 0))
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Left, 8, Boundary::Walls),
+            None
+        );
+        assert_eq!(
+            corner.offset_dir_bounded(Direction::Up, 7, Boundary::Walls),
+            Some(Position::new(7, 7))
+        );
+    }
+}
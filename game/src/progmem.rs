@@ -0,0 +1,77 @@
+//! Constant data kept in flash instead of being copied into the 8KB of RAM at startup.
+//!
+//! On the AVR flash is a separate address space which is read with `lpm`, on any other target
+//! the bytes are read as usual so everything using them can be tested on the host.
+
+/// Bytes in flash, declared with [`progmem!`](crate::progmem!):
+///
+/// ```
+/// use snake_game::{progmem, progmem::ProgMem};
+///
+/// progmem! {
+///     static DATA: ProgMem<3> = [1, 2, 3];
+/// }
+///
+/// assert_eq!(DATA.load(2), 3);
+/// ```
+#[repr(transparent)]
+pub struct ProgMem<const N: usize>([u8; N]);
+
+/// Declares a [`ProgMem`] static, placed in `.progmem.data` on the AVR so it stays in flash.
+#[macro_export]
+macro_rules! progmem {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $bytes:expr;) => {
+        $(#[$attr])*
+        #[cfg_attr(target_arch = "avr", link_section = ".progmem.data")]
+        // SAFETY: a static in `.progmem.data`, as `ProgMem::new` asks for
+        $vis static $name: $ty = unsafe { <$ty>::new($bytes) };
+    };
+}
+
+impl<const N: usize> ProgMem<N> {
+    /// Use [`progmem!`](crate::progmem!) instead.
+    ///
+    /// # Safety
+    ///
+    /// On the AVR the value has to be a static in `.progmem.data`. Anywhere else `load` reads
+    /// whatever is at the same address in flash.
+    #[doc(hidden)]
+    pub const unsafe fn new(bytes: [u8; N]) -> Self {
+        Self(bytes)
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Reads the byte at `idx`, panics if it is out of range like indexing an array would.
+    #[inline]
+    pub fn load(&self, idx: usize) -> u8 {
+        let byte = &self.0[idx];
+        read_byte(byte)
+    }
+}
+
+#[cfg(target_arch = "avr")]
+#[inline]
+fn read_byte(byte: &u8) -> u8 {
+    let value;
+    // SAFETY: `ProgMem` can only be made by `progmem!`, as a static in `.progmem.data`, so the
+    // address is in the low 64KB of flash `lpm` can reach
+    unsafe {
+        core::arch::asm!(
+            "lpm {}, Z",
+            out(reg) value,
+            in("Z") byte as *const u8,
+            options(pure, readonly, nostack, preserves_flags),
+        );
+    }
+    value
+}
+
+#[cfg(not(target_arch = "avr"))]
+#[inline]
+fn read_byte(byte: &u8) -> u8 {
+    *byte
+}
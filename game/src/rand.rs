@@ -0,0 +1,158 @@
+use crate::{position::Position, direction::Direction};
+
+// WyRand adds this to its state for every output and mixes it back in
+const WYRAND_INCREMENT: u64 = 0xE7037ED1A0B428DB;
+
+/// The WyRand generator, small and fast and more than random enough for a game.
+pub struct RandomGenerator {
+    state: u64,
+}
+
+impl RandomGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The seed of a generator which carries on from here, `new(state())` gives the same numbers
+    /// as this one from now on.
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// The raw 64 bit output of the WyRand generator.
+    pub fn generate(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(WYRAND_INCREMENT);
+        let x = self.state as u128 * (self.state ^ WYRAND_INCREMENT) as u128;
+        ((x >> 64) ^ x) as u64
+    }
+
+    /// A number in `min..max` with every value equally likely, `max` must be greater than `min`.
+    ///
+    /// Taking the output modulo the size of the range would favour the low values whenever the
+    /// size does not divide 2^64, so outputs from the last, incomplete, window are redrawn.
+    pub fn generate_range(&mut self, min: usize, max: usize) -> usize {
+        let span = (max - min) as u64;
+        // 2^64 % span, the outputs below it are the ones which do not fit a whole window
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let x = self.generate();
+            if x >= threshold {
+                return min + (x % span) as usize;
+            }
+        }
+    }
+}
+
+pub trait Random {
+    fn random(rand_gen: &mut RandomGenerator) -> Self;
+}
+
+impl<const X_LIM: isize, const Y_LIM: isize> Random for Position<X_LIM, Y_LIM> {
+    fn random(rand_gen: &mut RandomGenerator) -> Self {
+        let x = rand_gen.generate_range(0, X_LIM as usize) as u8;
+        let y = rand_gen.generate_range(0, Y_LIM as usize) as u8;
+        Self::new(x, y)
+    }
+}
+
+impl Random for Direction {
+    fn random(rand_gen: &mut RandomGenerator) -> Self {
+        Direction::ALL[rand_gen.generate_range(0, Direction::ALL.len())]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Random, RandomGenerator};
+    use crate::{direction::Direction, position::Position};
+
+    // Chi-squared statistic of the observed counts against a uniform distribution
+    fn chi_squared(counts: &[u32]) -> f64 {
+        let total: u32 = counts.iter().sum();
+        let expected = total as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    #[test]
+    fn wyrand() {
+        // Saved replays depend on these never changing
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        assert_eq!(rand_gen.generate(), 0x8B07_9DB4_0FF9_C2DE);
+        assert_eq!(rand_gen.generate(), 0x14A4_90F0_C731_570A);
+        assert_eq!(rand_gen.generate(), 0xA8BD_25DB_2961_BA13);
+    }
+
+    #[test]
+    fn carries_on_from_state() {
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        for _ in 0..10 {
+            rand_gen.generate();
+        }
+        let mut resumed = RandomGenerator::new(rand_gen.state());
+        for _ in 0..100 {
+            assert_eq!(resumed.generate(), rand_gen.generate());
+        }
+    }
+
+    #[test]
+    fn range_bounds() {
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        for _ in 0..1000 {
+            let x = rand_gen.generate_range(3, 10);
+            assert!((3..10).contains(&x));
+            assert_eq!(rand_gen.generate_range(250, 251), 250);
+        }
+    }
+
+    #[test]
+    fn range_is_uniform() {
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        let mut counts = [0; 7];
+        for _ in 0..70_000 {
+            counts[rand_gen.generate_range(0, 7)] += 1;
+        }
+        // 6 degrees of freedom, p = 0.001
+        assert!(chi_squared(&counts) < 22.46, "{counts:?}");
+    }
+
+    #[test]
+    fn every_cell_equally_likely() {
+        fn check<const X_LIM: isize, const Y_LIM: isize>(critical: f64) {
+            let cells = (X_LIM * Y_LIM) as usize;
+            let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+            let mut counts = vec![0; cells];
+            for _ in 0..cells * 1000 {
+                let pos = Position::<X_LIM, Y_LIM>::random(&mut rand_gen);
+                assert!((pos.x() as isize) < X_LIM && (pos.y() as isize) < Y_LIM);
+                counts[pos.y() as usize * X_LIM as usize + pos.x() as usize] += 1;
+            }
+            assert!(counts.iter().all(|&count| count > 0), "{counts:?}");
+            assert!(chi_squared(&counts) < critical, "{counts:?}");
+        }
+        // Critical values for p = 0.001 with one degree of freedom less than the cells
+        check::<8, 8>(103.44);
+        check::<16, 8>(172.04);
+        check::<32, 8>(330.52);
+    }
+
+    #[test]
+    fn every_direction_equally_likely() {
+        let mut rand_gen = RandomGenerator::new(0xDEADBEEF);
+        let mut counts = [0; 4];
+        for _ in 0..40_000 {
+            let idx = match Direction::random(&mut rand_gen) {
+                Direction::Up => 0,
+                Direction::Down => 1,
+                Direction::Left => 2,
+                Direction::Right => 3,
+            };
+            counts[idx] += 1;
+        }
+        assert!(counts.iter().all(|&count| count > 0), "{counts:?}");
+        // 3 degrees of freedom, p = 0.001
+        assert!(chi_squared(&counts) < 16.27, "{counts:?}");
+    }
+}
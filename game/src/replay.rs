@@ -0,0 +1,383 @@
+use ufmt::{uDisplay, uwrite};
+
+use super::{
+    direction::Direction, level::Level, position::Boundary, rand::RandomGenerator, Command, Game,
+    GameResult, Mode,
+};
+
+/// A game session: the seed of the `RandomGenerator`, the `Mode` of the game and every
+/// `Command` given to `Game::step`, enough to play the session again exactly as it happened with
+/// [`Recording::replay`].
+///
+/// The commands are run-length encoded as the same move is usually repeated for many steps.
+/// Up to `CAP` runs are stored, commands pushed after that are dropped.
+///
+/// A recording is written out as the seed followed by the runs, e.g. `3735928559 12R 3U 1X`,
+/// where `U`, `D`, `L` and `R` are moves, `X` is a reset and `P` and `G` pause and resume. The
+/// mode follows the seed unless it is the default, `walls` for walls at the edges and `levels`
+/// for playing through the levels, followed by the level to start on (counted from 1) unless it
+/// is the first. [`Recording::parse`] reads it back.
+#[derive(Debug, Clone)]
+pub struct Recording<const CAP: usize> {
+    seed: u64,
+    mode: Mode,
+    // Only played when `mode.levels` is set
+    level: Level,
+    runs: [Run; CAP],
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Run {
+    cmd: Command,
+    count: u8,
+}
+
+impl Default for Run {
+    fn default() -> Self {
+        Self {
+            cmd: Command::Reset,
+            count: 0,
+        }
+    }
+}
+
+impl<const CAP: usize> Recording<CAP> {
+    /// A recording of games in the default mode.
+    pub fn new(seed: u64) -> Self {
+        Self::with_mode(seed, Mode::default())
+    }
+
+    pub fn with_mode(seed: u64, mode: Mode) -> Self {
+        Self {
+            seed,
+            mode,
+            level: Level::first(),
+            runs: [Run::default(); CAP],
+            len: 0,
+        }
+    }
+
+    /// An empty recording which starts where `game` is now, made as soon as its snake has been
+    /// spawned.
+    pub fn of_game<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+        game: &Game<SNAKE_LEN, X_LIM, Y_LIM>,
+    ) -> Self {
+        let mut recording = Self::with_mode(game.start_seed(), game.mode());
+        recording.level = game.level().unwrap_or_else(Level::first);
+        recording
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The level the recording starts on, `None` unless playing through the levels.
+    pub fn level(&self) -> Option<Level> {
+        self.mode.levels.then_some(self.level)
+    }
+
+    /// A new game using the seed, mode and level of the recording.
+    pub fn new_game<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+        &self,
+    ) -> Game<SNAKE_LEN, X_LIM, Y_LIM> {
+        Game::at_level(
+            RandomGenerator::new(self.seed),
+            self.mode.boundary,
+            self.level(),
+        )
+    }
+
+    /// Appends a command, returns `false` if the recording is full and it was dropped.
+    pub fn push(&mut self, cmd: Command) -> bool {
+        if let Some(run) = self.runs[..self.len].last_mut() {
+            if run.cmd == cmd && run.count < u8::MAX {
+                run.count += 1;
+                return true;
+            }
+        }
+        if self.is_full() {
+            false
+        } else {
+            self.runs[self.len] = Run { cmd, count: 1 };
+            self.len += 1;
+            true
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == CAP
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// The recorded commands in order.
+    pub fn commands(&self) -> impl Iterator<Item = Command> + '_ {
+        self.runs[..self.len]
+            .iter()
+            .flat_map(|run| core::iter::repeat(run.cmd).take(run.count as usize))
+    }
+
+    /// Plays the recorded commands on a new game one step at a time.
+    pub fn replay<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+        &self,
+    ) -> Replay<impl Iterator<Item = Command> + '_, SNAKE_LEN, X_LIM, Y_LIM> {
+        Replay {
+            game: self.new_game(),
+            commands: self.commands(),
+        }
+    }
+
+    /// Reads a recording written out by its `uDisplay` implementation.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut words = s.split_whitespace().peekable();
+        let seed = words.next()?.parse().ok()?;
+        let boundary = match words.next_if_eq(&"walls") {
+            Some(_) => Boundary::Walls,
+            None => Boundary::Wrap,
+        };
+        let levels = words.next_if_eq(&"levels").is_some();
+        let mut recording = Self::with_mode(seed, Mode { boundary, levels });
+        // Runs always end in a command, so a plain number can only be the level
+        if let Some(number) = words.next_if(|word| levels && word.parse::<u8>().is_ok()) {
+            recording.level = Level::nth(number.parse::<u8>().ok()?.checked_sub(1)?)?;
+        }
+        for word in words {
+            // The command is the last character, which need not be a single byte in a mistyped one
+            let (count, cmd) = word.split_at(word.char_indices().last()?.0);
+            let cmd = match cmd {
+                "X" => Command::Reset,
+                "U" => Command::Move(Direction::Up),
+                "D" => Command::Move(Direction::Down),
+                "L" => Command::Move(Direction::Left),
+                "R" => Command::Move(Direction::Right),
+                "P" => Command::Pause,
+                "G" => Command::Resume,
+                _ => return None,
+            };
+            for _ in 0..count.parse::<u16>().ok()? {
+                if !recording.push(cmd) {
+                    return None;
+                }
+            }
+        }
+        Some(recording)
+    }
+}
+
+impl<const CAP: usize> uDisplay for Recording<CAP> {
+    fn fmt<W>(&self, f: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: ufmt::uWrite + ?Sized,
+    {
+        uwrite!(f, "{}", self.seed)?;
+        if self.mode.boundary == Boundary::Walls {
+            uwrite!(f, " walls")?;
+        }
+        if self.mode.levels {
+            uwrite!(f, " levels")?;
+        }
+        if let Some(level) = self.level().filter(|&level| level != Level::first()) {
+            uwrite!(f, " {}", level.index() + 1)?;
+        }
+        for run in self.runs[..self.len].iter() {
+            let cmd = match run.cmd {
+                Command::Reset => "X",
+                Command::Move(Direction::Up) => "U",
+                Command::Move(Direction::Down) => "D",
+                Command::Move(Direction::Left) => "L",
+                Command::Move(Direction::Right) => "R",
+                Command::Pause => "P",
+                Command::Resume => "G",
+            };
+            uwrite!(f, " {}{}", run.count, cmd)?;
+        }
+        Ok(())
+    }
+}
+
+/// A recording being played back, see [`Recording::replay`]. Every step yields the result of
+/// the recorded command.
+pub struct Replay<I, const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize> {
+    game: Game<SNAKE_LEN, X_LIM, Y_LIM>,
+    commands: I,
+}
+
+impl<I, const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>
+    Replay<I, SNAKE_LEN, X_LIM, Y_LIM>
+{
+    /// The game as of the last step.
+    pub fn game(&self) -> &Game<SNAKE_LEN, X_LIM, Y_LIM> {
+        &self.game
+    }
+}
+
+impl<I, const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize> Iterator
+    for Replay<I, SNAKE_LEN, X_LIM, Y_LIM>
+where
+    I: Iterator<Item = Command>,
+{
+    type Item = (Command, GameResult);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cmd = self.commands.next()?;
+        Some((cmd, self.game.step(cmd)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Recording;
+    use crate::{
+        direction::Direction,
+        level::Level,
+        position::Boundary,
+        rand::{Random, RandomGenerator},
+        test_util::render,
+        Command, Game, GameResult, Mode,
+    };
+
+    #[test]
+    fn run_length() {
+        let mut recording = Recording::<2>::new(7);
+        for _ in 0..300 {
+            assert!(recording.push(Command::Move(Direction::Up)));
+        }
+        assert!(recording.is_full());
+        assert!(recording.push(Command::Move(Direction::Up)));
+        assert!(!recording.push(Command::Reset));
+        assert_eq!(recording.commands().count(), 301);
+        assert_eq!(render(&recording), "7 255U 46U");
+    }
+
+    #[test]
+    fn parse() {
+        let recording = Recording::<8>::parse("3735928559 12R 3U 1P 1G 1X 2L").unwrap();
+        assert_eq!(recording.seed(), 0xDEADBEEF);
+        assert_eq!(recording.commands().count(), 20);
+        assert_eq!(render(&recording), "3735928559 12R 3U 1P 1G 1X 2L");
+        assert_eq!(
+            Recording::<8>::parse(&render(&recording))
+                .unwrap()
+                .commands()
+                .collect::<Vec<_>>(),
+            recording.commands().collect::<Vec<_>>()
+        );
+
+        let walls = Recording::<8>::parse("3735928559 walls 12R").unwrap();
+        assert_eq!(walls.mode().boundary, Boundary::Walls);
+        assert!(!walls.mode().levels);
+        assert_eq!(walls.commands().count(), 12);
+        assert_eq!(render(&walls), "3735928559 walls 12R");
+        assert_eq!(recording.mode(), Mode::default());
+
+        let levels = Recording::<8>::parse("3735928559 walls levels 1D").unwrap();
+        let mode = Mode {
+            boundary: Boundary::Walls,
+            levels: true,
+        };
+        assert_eq!(levels.mode(), mode);
+        assert_eq!(render(&levels), "3735928559 walls levels 1D");
+        assert!(Recording::<8>::parse("3735928559 levels walls 1D").is_none());
+
+        let third = Recording::<8>::parse("3735928559 levels 3 1D").unwrap();
+        assert_eq!(third.level().map(Level::index), Some(2));
+        assert_eq!(render(&third), "3735928559 levels 3 1D");
+        assert_eq!(
+            render(&Recording::<8>::parse("1 levels 1").unwrap()),
+            "1 levels"
+        );
+        assert!(Recording::<8>::parse("1 levels 0").is_none());
+        assert!(Recording::<8>::parse("1 levels 99").is_none());
+        assert!(Recording::<8>::parse("1 3 1D").is_none());
+
+        assert!(Recording::<8>::parse("").is_none());
+        assert!(Recording::<8>::parse("12 3Q").is_none());
+        assert!(Recording::<8>::parse("12 R").is_none());
+        assert!(Recording::<8>::parse("1 1é").is_none());
+        assert!(Recording::<1>::parse("12 1R 1L").is_none());
+    }
+
+    #[test]
+    fn replay_matches_session() {
+        for boundary in [Boundary::Wrap, Boundary::Walls] {
+            for levels in [false, true] {
+                check_replay(Mode { boundary, levels });
+            }
+        }
+    }
+
+    fn check_replay(mode: Mode) {
+        let mut recording = Recording::<4096>::with_mode(0xDEADBEEF, mode);
+        let mut game: Game = recording.new_game();
+        let mut input = RandomGenerator::new(42);
+        let mut results = Vec::new();
+        for i in 0..2000 {
+            let cmd = if i % 500 == 499 {
+                Command::Reset
+            } else if i % 300 == 100 {
+                Command::Pause
+            } else if i % 300 == 110 {
+                Command::Resume
+            } else {
+                Command::Move(Direction::random(&mut input))
+            };
+            assert!(recording.push(cmd));
+            results.push(render(&game));
+            game.step(cmd);
+        }
+
+        // Go through the written out form as a bug report would
+        let recording = Recording::<4096>::parse(&render(&recording)).unwrap();
+        let mut replay = recording.replay::<20, 8, 8>();
+        for expected in results.iter() {
+            assert_eq!(&render(replay.game()), expected);
+            assert!(replay.next().is_some());
+        }
+        assert!(replay.next().is_none());
+        assert_eq!(render(replay.game()), render(&game));
+    }
+
+    #[test]
+    fn recording_of_each_game() {
+        let levels = Mode {
+            boundary: Boundary::Walls,
+            levels: true,
+        };
+        for mode in [Mode::default(), levels] {
+            let mut game: Game = Game::with_mode(RandomGenerator::new(7), mode);
+            if mode.levels {
+                // A death keeps the snake on the level it was on
+                game.level = Level::nth(2);
+                game.board.load(game.level);
+                game.restart();
+            }
+            let mut recording = Recording::<4096>::of_game(&game);
+            let mut input = RandomGenerator::new(42);
+            let mut games = 0;
+            for _ in 0..2000 {
+                let cmd = Command::Move(Direction::random(&mut input));
+                assert!(recording.push(cmd));
+                if game.step(cmd) != GameResult::Died {
+                    continue;
+                }
+                // The recording of just this game plays out to the same death
+                let written = Recording::<4096>::parse(&render(&recording)).unwrap();
+                assert_eq!(written.level(), game.level());
+                let mut replay = written.replay::<20, 8, 8>();
+                assert_eq!(replay.by_ref().last().unwrap().1, GameResult::Died);
+                assert_eq!(replay.game().last_score(), game.last_score());
+                assert_eq!(render(replay.game()), render(&game));
+                recording = Recording::of_game(&game);
+                games += 1;
+            }
+            assert!(games > 1);
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Turning stick readings into directions. The axis pushed furthest wins, and once a direction
+//! is picked it sticks until the stick is clearly pushed some other way, so a stick held on a
+//! diagonal doesn't flicker between directions. Anything outside the deadzone of the
+//! [`Calibration`](crate::calibration::Calibration) picks a direction from rest.
+
+use super::direction::Direction;
+
+/// How much further another direction has to be pushed than the current one to take over, out
+/// of the `FULL_DEFLECTION` of [`Axis::deflection`](crate::calibration::Axis::deflection).
+pub const SWITCH_MARGIN: i16 = 25;
+
+#[derive(Debug, Default, Clone)]
+pub struct Resolver {
+    current: Option<Direction>,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The direction of the last reading.
+    pub fn direction(&self) -> Option<Direction> {
+        self.current
+    }
+
+    /// Takes a reading where `x` grows to the right and `y` grows upwards, like `Position`, and
+    /// 0 is inside the deadzone. The current direction is kept for as long as the stick is
+    /// pushed that way at all, unless another is pushed `SWITCH_MARGIN` further.
+    pub fn resolve(&mut self, x: i16, y: i16) -> Option<Direction> {
+        let towards = |dir| match dir {
+            Direction::Right => x,
+            Direction::Left => -x,
+            Direction::Up => y,
+            Direction::Down => -y,
+        };
+        // x wins a tie, there is no telling which way an exact diagonal was meant
+        let dominant = if x.unsigned_abs() >= y.unsigned_abs() {
+            if x >= 0 {
+                Direction::Right
+            } else {
+                Direction::Left
+            }
+        } else if y >= 0 {
+            Direction::Up
+        } else {
+            Direction::Down
+        };
+        let strength = towards(dominant);
+
+        self.current = match self.current {
+            Some(current) if towards(current) > 0 => {
+                if strength >= towards(current).saturating_add(SWITCH_MARGIN) {
+                    Some(dominant)
+                } else {
+                    Some(current)
+                }
+            }
+            _ if strength > 0 => Some(dominant),
+            _ => None,
+        };
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Resolver, SWITCH_MARGIN};
+    use crate::{
+        calibration::Axis,
+        direction::Direction::{self, *},
+    };
+
+    /// The direction after each reading of `readings`, starting from rest.
+    fn resolve(readings: &[(i16, i16)]) -> Vec<Option<Direction>> {
+        let mut resolver = Resolver::new();
+        readings
+            .iter()
+            .map(|&(x, y)| resolver.resolve(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn dominant_axis() {
+        assert_eq!(resolve(&[(0, 0)]), [None]);
+        assert_eq!(resolve(&[(60, 20)]), [Some(Right)]);
+        assert_eq!(resolve(&[(-60, 20)]), [Some(Left)]);
+        // A diagonal leaning up goes up, rather than always left or right
+        assert_eq!(resolve(&[(40, 70)]), [Some(Up)]);
+        assert_eq!(resolve(&[(-40, -70)]), [Some(Down)]);
+        assert_eq!(resolve(&[(50, 50)]), [Some(Right)]);
+        // Anything out of the deadzone is enough to start
+        assert_eq!(resolve(&[(1, 0)]), [Some(Right)]);
+        assert_eq!(resolve(&[(0, -1)]), [Some(Down)]);
+    }
+
+    #[test]
+    fn quarter_of_the_way() {
+        // Without a calibration a quarter of the range either side of 500, as the README says
+        let axis = Axis::default();
+        let (rest, pushed) = (axis.deflection(750), axis.deflection(751));
+        assert_eq!(resolve(&[(rest, 0)]), [None]);
+        assert_eq!(resolve(&[(pushed, 0)]), [Some(Right)]);
+        assert_eq!(resolve(&[(0, -axis.deflection(249))]), [Some(Up)]);
+        assert_eq!(resolve(&[(0, -axis.deflection(250))]), [None]);
+    }
+
+    #[test]
+    fn holds_direction() {
+        // Wobbling around a diagonal doesn't flicker
+        let wobble = [(60, 50), (55, 62), (62, 58), (50, 70), (58, 60)];
+        assert_eq!(resolve(&wobble), [Some(Right); 5]);
+        // Until the other axis is pushed clearly further
+        assert_eq!(
+            resolve(&[
+                (60, 50),
+                (40, 40 + SWITCH_MARGIN - 1),
+                (40, 40 + SWITCH_MARGIN)
+            ]),
+            [Some(Right), Some(Right), Some(Up)]
+        );
+        // Easing off but still pushed keeps it, back in the deadzone lets go
+        assert_eq!(
+            resolve(&[(0, 80), (0, 3), (0, 0), (0, -5)]),
+            [Some(Up), Some(Up), None, Some(Down)]
+        );
+    }
+
+    #[test]
+    fn reverses() {
+        // Flicked straight across, no margin needed once the old direction is let go of
+        assert_eq!(
+            resolve(&[(-90, 0), (-20, 5), (30, 0), (100, 0)]),
+            [Some(Left), Some(Left), Some(Right), Some(Right)]
+        );
+        // Round the edge of the stick
+        let circle = [
+            (100, 0),
+            (70, 70),
+            (0, 100),
+            (-70, 70),
+            (-100, 0),
+            (-70, -70),
+        ];
+        assert_eq!(
+            resolve(&circle),
+            [
+                Some(Right),
+                Some(Right),
+                Some(Up),
+                Some(Up),
+                Some(Left),
+                Some(Left)
+            ]
+        );
+    }
+}
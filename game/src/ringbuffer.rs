@@ -116,6 +116,15 @@ where
     }
 }
 
+impl<T, const CAP: usize> Default for RingBuffer<T, CAP>
+where
+    T: Default + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug)]
 pub struct RingBufferIter<'a, T, const CAP: usize>
 where
@@ -0,0 +1,38 @@
+use core::cmp::Ordering;
+
+/// How well a game went. Scores compare by apples eaten, between the same number of apples the
+/// one which took fewer steps is better.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub apples: u16,
+    /// Steps the snake moved.
+    pub ticks: u32,
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.apples
+            .cmp(&other.apples)
+            .then_with(|| other.ticks.cmp(&self.ticks))
+    }
+}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Score;
+
+    #[test]
+    fn ordering() {
+        let score = |apples, ticks| Score { apples, ticks };
+        assert!(score(5, 100) > score(4, 10));
+        assert!(score(5, 100) > score(5, 120));
+        assert_eq!(score(3, 20).max(score(3, 21)), score(3, 20));
+        assert!(Score::default() < score(1, 1000));
+    }
+}
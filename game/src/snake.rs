@@ -0,0 +1,171 @@
+use super::ringbuffer::{RingBuffer, RingBufferIter};
+
+use super::{board::Board, direction::Direction, occupancy::Occupancy, position::Position};
+
+#[derive(Debug)]
+pub struct Snake<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize> {
+    body: RingBuffer<Position<X_LIM, Y_LIM>, SNAKE_LEN>,
+    // The cells covered by `body`, kept in step with it so lookups do not scan the body
+    occupied: Occupancy<X_LIM, Y_LIM>,
+    current_dir: Direction,
+    snake_tail: Position<X_LIM, Y_LIM>,
+}
+
+pub enum MovementResult {
+    BitSelf,
+    HitWall,
+    Moving,
+    AteApple,
+    AteAppleAndMaxed,
+}
+
+impl<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>
+    Snake<SNAKE_LEN, X_LIM, Y_LIM>
+{
+    pub fn new(start_pos: Position<X_LIM, Y_LIM>, len: u8, dir: Direction) -> Self {
+        let mut body = RingBuffer::new();
+        let mut occupied = Occupancy::new();
+        for i in 0..(len as isize) {
+            let pos = start_pos.offset_dir_scaled(dir, i);
+            body.push(pos);
+            occupied.set(&pos, true);
+        }
+        Self {
+            body,
+            occupied,
+            current_dir: dir,
+            snake_tail: Default::default(),
+        }
+    }
+
+    pub fn move_dir(
+        &mut self,
+        dir: Direction,
+        apple_pos: Position<X_LIM, Y_LIM>,
+        board: &Board<X_LIM, Y_LIM>,
+    ) -> MovementResult {
+        let dir = if dir.is_opposing(self.current_dir) {
+            dir.get_opposite()
+        } else {
+            dir
+        };
+
+        let current = self.body.peek_back().unwrap();
+        let head = match board.offset(current, dir, 1) {
+            Some(head) => head,
+            None => return MovementResult::HitWall,
+        };
+        if self.check_overlap(&head) {
+            return MovementResult::BitSelf;
+        }
+        self.body.push(head);
+        self.occupied.set(&head, true);
+        self.current_dir = dir;
+        if apple_pos == head {
+            if self.body.is_full() {
+                MovementResult::AteAppleAndMaxed
+            } else {
+                MovementResult::AteApple
+            }
+        } else {
+            if let Some(pos) = self.body.pop() {
+                self.occupied.set(&pos, false);
+                self.snake_tail = pos
+            }
+            MovementResult::Moving
+        }
+    }
+
+    #[inline]
+    pub fn check_overlap(&self, apple: &Position<X_LIM, Y_LIM>) -> bool {
+        self.occupied.is_set(apple)
+    }
+
+    /// Every cell on the board not covered by the snake, row by row.
+    pub fn free_cells(&self) -> impl Iterator<Item = Position<X_LIM, Y_LIM>> + '_ {
+        (0..Y_LIM)
+            .flat_map(|y| (0..X_LIM).map(move |x| Position::new(x as u8, y as u8)))
+            .filter(|pos| !self.check_overlap(pos))
+    }
+
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn iter(&self) -> RingBufferIter<'_, Position<X_LIM, Y_LIM>, SNAKE_LEN> {
+        self.body.iter()
+    }
+
+    pub fn current_dir(&self) -> Direction {
+        self.current_dir
+    }
+
+    pub fn snake_tail(&self) -> &Position<X_LIM, Y_LIM> {
+        &self.snake_tail
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MovementResult, Snake};
+    use crate::{
+        board::Board,
+        direction::Direction,
+        position::{Boundary, Position},
+    };
+
+    #[test]
+    fn occupancy_follows_body() {
+        let mut snake = Snake::<20, 8, 8>::new(Position::new(2, 2), 3, Direction::Right);
+        let moves = [
+            Direction::Right,
+            Direction::Up,
+            Direction::Up,
+            Direction::Left,
+            Direction::Left,
+            Direction::Down,
+        ];
+        let board = Board::new(Boundary::Wrap);
+        // An apple in front of the head every other move to grow the body as well
+        for (i, &dir) in moves.iter().cycle().take(30).enumerate() {
+            let head = snake.body.peek_back().unwrap().offset_dir(dir);
+            let apple = if i % 2 == 0 {
+                head
+            } else {
+                Position::default()
+            };
+            if let MovementResult::BitSelf = snake.move_dir(dir, apple, &board) {
+                break;
+            }
+            assert_eq!(snake.occupied.count(), snake.body.len());
+            for pos in snake.iter() {
+                assert!(snake.check_overlap(pos));
+            }
+        }
+    }
+
+    #[test]
+    fn walls() {
+        let mut snake = Snake::<20, 8, 8>::new(Position::new(4, 4), 3, Direction::Right);
+        let apple = Position::new(0, 0);
+        let (walls, wrap) = (Board::new(Boundary::Walls), Board::new(Boundary::Wrap));
+        assert!(matches!(
+            snake.move_dir(Direction::Right, apple, &walls),
+            MovementResult::Moving
+        ));
+        assert!(matches!(
+            snake.move_dir(Direction::Right, apple, &walls),
+            MovementResult::HitWall
+        ));
+        // Nothing moved
+        assert_eq!(snake.iter().last(), Some(&Position::new(7, 4)));
+        assert_eq!(snake.occupied.count(), 3);
+
+        assert!(matches!(
+            snake.move_dir(Direction::Right, apple, &wrap),
+            MovementResult::Moving
+        ));
+        assert_eq!(snake.iter().last(), Some(&Position::new(0, 4)));
+    }
+}
@@ -0,0 +1,83 @@
+/// How long each step of the game lasts, getting shorter as apples are eaten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Speed {
+    /// The interval at the start of a game, in milliseconds.
+    pub start_ms: u16,
+    /// How much shorter the interval gets every `apples_per_step` apples.
+    pub step_ms: u16,
+    pub apples_per_step: u16,
+    /// The interval never gets shorter than this.
+    pub floor_ms: u16,
+}
+
+impl Default for Speed {
+    /// Starts at the 100ms the game always ran at, 10ms faster every 3 apples down to 60ms.
+    fn default() -> Self {
+        Self {
+            start_ms: 100,
+            step_ms: 10,
+            apples_per_step: 3,
+            floor_ms: 60,
+        }
+    }
+}
+
+impl Speed {
+    /// The same interval however many apples are eaten.
+    pub const fn constant(interval_ms: u16) -> Self {
+        Self {
+            start_ms: interval_ms,
+            step_ms: 0,
+            apples_per_step: 1,
+            floor_ms: interval_ms,
+        }
+    }
+
+    /// The interval between steps once `apples` have been eaten.
+    pub fn interval_ms(&self, apples: u16) -> u16 {
+        let steps = apples.checked_div(self.apples_per_step).unwrap_or(0);
+        self.start_ms
+            .saturating_sub(steps.saturating_mul(self.step_ms))
+            .max(self.floor_ms)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Speed;
+
+    #[test]
+    fn default_curve() {
+        let speed = Speed::default();
+        let intervals: Vec<_> = (0..16).map(|apples| speed.interval_ms(apples)).collect();
+        assert_eq!(
+            intervals,
+            [100, 100, 100, 90, 90, 90, 80, 80, 80, 70, 70, 70, 60, 60, 60, 60]
+        );
+        assert_eq!(speed.interval_ms(u16::MAX), 60);
+    }
+
+    #[test]
+    fn floor_and_constant() {
+        let speed = Speed {
+            start_ms: 250,
+            step_ms: 40,
+            apples_per_step: 2,
+            floor_ms: 100,
+        };
+        assert_eq!(speed.interval_ms(1), 250);
+        assert_eq!(speed.interval_ms(2), 210);
+        assert_eq!(speed.interval_ms(6), 130);
+        // 90 is below the floor
+        assert_eq!(speed.interval_ms(8), 100);
+
+        let constant = Speed::constant(120);
+        assert!((0..100).all(|apples| constant.interval_ms(apples) == 120));
+        // Never speeds up rather than dividing by zero
+        let never = Speed {
+            apples_per_step: 0,
+            ..Speed::default()
+        };
+        assert_eq!(never.interval_ms(50), 100);
+    }
+}
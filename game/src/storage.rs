@@ -0,0 +1,74 @@
+//! Non-volatile memory, the EEPROM on the board, which the high scores and the stick calibration
+//! are saved to.
+
+/// Byte addressed memory which keeps its contents without power.
+pub trait Storage {
+    /// Fills `buf` with the bytes starting at `addr`.
+    fn read(&mut self, addr: u16, buf: &mut [u8]);
+    /// Writes `data` starting at `addr`.
+    fn write(&mut self, addr: u16, data: &[u8]);
+}
+
+/// The checksum closing every record, so a blank or half written one is never loaded.
+#[derive(Default)]
+pub(crate) struct Fletcher16 {
+    low: u16,
+    high: u16,
+}
+
+impl Fletcher16 {
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.low = (self.low + byte as u16) % 255;
+            self.high = (self.high + self.low) % 255;
+        }
+    }
+
+    pub(crate) fn value(&self) -> u16 {
+        (self.high << 8) | self.low
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::{Fletcher16, Storage};
+
+    /// An EEPROM as it comes, every byte erased to 0xFF.
+    pub(crate) struct FakeEeprom(pub [u8; 256]);
+
+    impl FakeEeprom {
+        pub(crate) fn new() -> Self {
+            Self([0xFF; 256])
+        }
+    }
+
+    impl Storage for FakeEeprom {
+        fn read(&mut self, addr: u16, buf: &mut [u8]) {
+            let addr = addr as usize;
+            buf.copy_from_slice(&self.0[addr..addr + buf.len()]);
+        }
+
+        fn write(&mut self, addr: u16, data: &[u8]) {
+            let addr = addr as usize;
+            self.0[addr..addr + data.len()].copy_from_slice(data);
+        }
+    }
+
+    #[test]
+    fn fletcher16() {
+        // The check values from the definition
+        let checksum = |data: &[u8]| {
+            let mut sum = Fletcher16::default();
+            sum.update(data);
+            sum.value()
+        };
+        assert_eq!(checksum(b"abcde"), 0xC8F0);
+        assert_eq!(checksum(b"abcdef"), 0x2057);
+        assert_eq!(checksum(b"abcdefgh"), 0x0627);
+        // Split up the same as in one go
+        let mut sum = Fletcher16::default();
+        sum.update(b"abc");
+        sum.update(b"def");
+        assert_eq!(sum.value(), 0x2057);
+    }
+}
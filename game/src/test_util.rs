@@ -0,0 +1,19 @@
+//! Helpers shared by the tests of several modules.
+
+struct Screen(String);
+
+impl ufmt::uWrite for Screen {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+/// What `value` writes out through its `uDisplay` implementation.
+pub(crate) fn render<T: ufmt::uDisplay>(value: &T) -> String {
+    let mut screen = Screen(String::new());
+    ufmt::uwrite!(screen, "{}", value).unwrap();
+    screen.0
+}
@@ -0,0 +1,119 @@
+//! Turns read between steps, so a quick Up then Left within one step is taken as both rather
+//! than just the last.
+
+use super::{direction::Direction, ringbuffer::RingBuffer};
+
+/// Up to `CAP` turns waiting for the snake, one taken every step.
+#[derive(Debug, Default)]
+pub struct TurnQueue<const CAP: usize> {
+    turns: RingBuffer<Direction, CAP>,
+}
+
+impl<const CAP: usize> TurnQueue<CAP> {
+    pub fn new() -> Self {
+        Self {
+            turns: RingBuffer::new(),
+        }
+    }
+
+    /// Queues `dir` after the turns already waiting, `heading` being the way the snake goes now.
+    /// Returns `false` if it was dropped: the queue is full, or `dir` is the way the snake would
+    /// already be going after the turns before it, or straight back the way it came.
+    pub fn push(&mut self, dir: Direction, heading: Direction) -> bool {
+        let last = self.turns.peek_back().copied().unwrap_or(heading);
+        if dir == last || dir.is_opposing(last) {
+            return false;
+        }
+        self.turns.push(dir)
+    }
+
+    /// The way to go for the next step, the oldest turn waiting or otherwise straight on.
+    pub fn next(&mut self, heading: Direction) -> Direction {
+        self.turns.pop().unwrap_or(heading)
+    }
+
+    /// Drops every turn waiting, for when the snake starts over.
+    pub fn clear(&mut self) {
+        self.turns.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TurnQueue;
+    use crate::{
+        direction::Direction::{self, *},
+        position::Position,
+        rand::RandomGenerator,
+        Command, Game, GameResult,
+    };
+
+    #[test]
+    fn in_order() {
+        let mut turns = TurnQueue::<3>::new();
+        assert_eq!(turns.next(Right), Right);
+        assert!(turns.push(Up, Right));
+        assert!(turns.push(Left, Right));
+        assert_eq!(turns.len(), 2);
+        assert_eq!(turns.next(Right), Up);
+        assert_eq!(turns.next(Up), Left);
+        assert!(turns.is_empty());
+        assert_eq!(turns.next(Left), Left);
+    }
+
+    #[test]
+    fn drops_pointless_turns() {
+        let mut turns = TurnQueue::<3>::new();
+        // Already going that way, or straight back into the snake
+        assert!(!turns.push(Right, Right));
+        assert!(!turns.push(Left, Right));
+        // Against the last turn queued rather than the heading
+        assert!(turns.push(Down, Right));
+        assert!(!turns.push(Down, Right));
+        assert!(!turns.push(Up, Right));
+        assert!(turns.push(Left, Right));
+        assert!(turns.push(Up, Right));
+        // Full
+        assert!(!turns.push(Right, Right));
+        assert_eq!(turns.len(), 3);
+
+        turns.clear();
+        assert_eq!(turns.next(Right), Right);
+    }
+
+    /// A turn and then back the way the snake came read within one step, where keeping only the
+    /// last would have been taken as a reverse into the snake.
+    #[test]
+    fn quick_turns() {
+        let mut game: Game = Game::new(RandomGenerator::new(7));
+        let mut turns = TurnQueue::<3>::new();
+        let heading = game.heading();
+        let turn = if matches!(heading, Left | Right) {
+            Up
+        } else {
+            Right
+        };
+        let back = heading.get_opposite();
+        assert!(turns.push(turn, heading));
+        assert!(turns.push(back, heading));
+
+        let head = |game: &Game| -> Position<8, 8> { *game.iter_snake().last().unwrap() };
+        let start = head(&game);
+        for expected in [turn, back, back] {
+            let dir: Direction = turns.next(game.heading());
+            assert_eq!(dir, expected);
+            assert_ne!(game.step(Command::Move(dir)), GameResult::Died);
+            assert_eq!(game.heading(), expected);
+        }
+        let end = start.offset_dir(turn).offset_dir(back).offset_dir(back);
+        assert_eq!(head(&game), end);
+    }
+}
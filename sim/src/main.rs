@@ -0,0 +1,284 @@
+//! Host-side simulator for the snake game.
+//!
+//! Drives the same `Game` the firmware runs with keyboard input and renders the board in the
+//! terminal through the `uDisplay` implementation of `Game`, so the layout matches the serial
+//! output of the Arduino.
+//!
+//! Usage: `cargo run -p snake-sim -- [seed] [board] [walls] [levels]`, the seed may be decimal or
+//! `0x` prefixed hex and the board one of `8x8` (the default), `16x8` or `32x8`. `walls` makes the
+//! edges of the board walls and `levels` plays through the built-in levels. They can be given in
+//! any order.
+//!
+//! The session is printed on quit as a recording, in the same form as the recording of a game
+//! the firmware prints over serial when the snake dies. `cargo run -p snake-sim -- replay "<recording>" [board]` plays one back.
+
+use std::{
+    io::{self, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyModifiers},
+    execute, queue,
+    terminal::{self, ClearType},
+};
+use snake_game::{
+    direction::Direction, position::Boundary, replay::Recording, turns::TurnQueue, Command, Game,
+    GameResult, Mode,
+};
+
+const PLAY_HELP: &str = "Arrows/WASD: move, P: pause, Space: reset, Q: quit";
+const REPLAY_HELP: &str = "Q: quit";
+
+// Runs of the same command, a long session fills about one per turn
+const RECORDING_CAP: usize = 4096;
+
+type SimRecording = Recording<RECORDING_CAP>;
+
+enum Input {
+    Quit,
+    Reset,
+    Pause,
+    Move(Direction),
+}
+
+fn main() -> io::Result<()> {
+    let mut args = std::env::args().skip(1).peekable();
+    let replay = args.next_if(|arg| arg == "replay").is_some();
+    let recording = match args.next_if(|_| replay) {
+        Some(arg) => Some(SimRecording::parse(&arg).ok_or_else(|| invalid_arg("recording", &arg))?),
+        None if replay => return Err(invalid_arg("recording", "")),
+        None => None,
+    };
+    // The rest are told apart by their form, so they can come in any order
+    let mut seed = None;
+    let mut board = None;
+    let mut mode = Mode::default();
+    for arg in args {
+        match arg.as_str() {
+            _ if board.is_none() && is_board(&arg) => board = Some(arg),
+            // A replay has the seed and mode in the recording
+            _ if replay => return Err(invalid_arg("argument", &arg)),
+            "walls" => mode.boundary = Boundary::Walls,
+            "levels" => mode.levels = true,
+            _ => match parse_seed(&arg) {
+                Some(value) if seed.is_none() => seed = Some(value),
+                _ => return Err(invalid_arg("argument", &arg)),
+            },
+        }
+    }
+    let board = board.unwrap_or_else(|| String::from("8x8"));
+    // The snake gets longer with the board so winning takes about as long
+    let (run, play): (fn(_, _) -> _, fn(_, _) -> _) = match board.as_str() {
+        "8x8" => (run::<20, 8, 8>, play::<20, 8, 8>),
+        "16x8" => (run::<40, 16, 8>, play::<40, 16, 8>),
+        "32x8" => (run::<80, 32, 8>, play::<80, 32, 8>),
+        _ => return Err(invalid_arg("board", &board)),
+    };
+    let recording = recording.unwrap_or_else(|| {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|time| time.as_nanos() as u64)
+                .unwrap_or(0xDEADBEEF)
+        });
+        SimRecording::with_mode(seed, mode)
+    });
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = if replay {
+        play(&mut stdout, &recording).map(|_| None)
+    } else {
+        run(&mut stdout, recording).map(Some)
+    };
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    if let Some(recording) = result? {
+        let mut line = String::new();
+        ufmt::uwrite!(&mut line, "{}", recording).unwrap();
+        if recording.is_full() {
+            println!("Recording full, truncated");
+        }
+        println!("Recording: {line}");
+    }
+    Ok(())
+}
+
+fn invalid_arg(name: &str, arg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid {name}: {arg}"),
+    )
+}
+
+/// Whether `arg` is a width and a height, e.g. `16x8`. The width of a board never starts with a 0,
+/// unlike a hex seed.
+fn is_board(arg: &str) -> bool {
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    arg.split_once('x').map_or(false, |(width, height)| {
+        is_number(width) && !width.starts_with('0') && is_number(height)
+    })
+}
+
+fn parse_seed(arg: &str) -> Option<u64> {
+    match arg.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => arg.parse().ok(),
+    }
+}
+
+fn run<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    stdout: &mut io::Stdout,
+    mut recording: SimRecording,
+) -> io::Result<SimRecording> {
+    let seed = recording.seed();
+    let mut game: Game<SNAKE_LEN, X_LIM, Y_LIM> = recording.new_game();
+    let mut turns: TurnQueue<3> = TurnQueue::new();
+    let mut status = "";
+
+    loop {
+        render(stdout, &game, seed, status, PLAY_HELP)?;
+
+        // Same as the firmware, every turn read during the tick is taken in order, one per step.
+        // A key has no long press, so space stands in for one and resets at the next step
+        let mut pressed = false;
+        let mut pause = false;
+        let deadline = Instant::now() + tick(&game);
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            match read_input(timeout)? {
+                Some(Input::Quit) => return Ok(recording),
+                Some(Input::Reset) => pressed = true,
+                Some(Input::Pause) => pause = !pause,
+                Some(Input::Move(dir)) if !game.is_paused() => {
+                    turns.push(dir, game.heading());
+                }
+                _ => {}
+            }
+        }
+        let cmd = if pressed {
+            Command::Reset
+        } else if pause {
+            if game.is_paused() {
+                Command::Resume
+            } else {
+                Command::Pause
+            }
+        } else if game.is_paused() {
+            // Nothing to record until it is resumed
+            continue;
+        } else {
+            Command::Move(turns.next(game.heading()))
+        };
+
+        recording.push(cmd);
+        let result = game.step(cmd);
+        if result != GameResult::Continue {
+            turns.clear();
+        }
+        status = describe(result);
+    }
+}
+
+/// Plays a recording back at the speed it was played, any key but quit is ignored.
+fn play<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    stdout: &mut io::Stdout,
+    recording: &SimRecording,
+) -> io::Result<()> {
+    let mut replay = recording.replay::<SNAKE_LEN, X_LIM, Y_LIM>();
+    let mut status = "Replaying...";
+
+    loop {
+        render(stdout, replay.game(), recording.seed(), status, REPLAY_HELP)?;
+
+        let deadline = Instant::now() + tick(replay.game());
+        while let Some(timeout) = deadline.checked_duration_since(Instant::now()) {
+            if let Some(Input::Quit) = read_input(timeout)? {
+                return Ok(());
+            }
+        }
+
+        status = match replay.next() {
+            Some((_, result)) => describe(result),
+            None => "Replay finished",
+        };
+    }
+}
+
+// As on the firmware the game decides how long to wait between steps
+fn tick<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    game: &Game<SNAKE_LEN, X_LIM, Y_LIM>,
+) -> Duration {
+    Duration::from_millis(game.tick_interval_ms().into())
+}
+
+fn describe(result: GameResult) -> &'static str {
+    match result {
+        GameResult::Continue => "",
+        GameResult::Died => "Died...",
+        GameResult::Won => "Won...",
+        GameResult::Restarting => "Restarting...",
+        GameResult::NextLevel => "Next level...",
+        GameResult::Paused => "Paused...",
+    }
+}
+
+fn read_input(timeout: Duration) -> io::Result<Option<Input>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    let key = match event::read()? {
+        Event::Key(key) => key,
+        _ => return Ok(None),
+    };
+    let input = match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Input::Quit,
+        KeyCode::Char('q') | KeyCode::Esc => Input::Quit,
+        KeyCode::Char(' ') | KeyCode::Enter => Input::Reset,
+        KeyCode::Char('p') => Input::Pause,
+        KeyCode::Up | KeyCode::Char('w') => Input::Move(Direction::Up),
+        KeyCode::Down | KeyCode::Char('s') => Input::Move(Direction::Down),
+        KeyCode::Left | KeyCode::Char('a') => Input::Move(Direction::Left),
+        KeyCode::Right | KeyCode::Char('d') => Input::Move(Direction::Right),
+        _ => return Ok(None),
+    };
+    Ok(Some(input))
+}
+
+fn render<const SNAKE_LEN: usize, const X_LIM: isize, const Y_LIM: isize>(
+    stdout: &mut io::Stdout,
+    game: &Game<SNAKE_LEN, X_LIM, Y_LIM>,
+    seed: u64,
+    status: &str,
+    help: &str,
+) -> io::Result<()> {
+    let mut board = String::new();
+    ufmt::uwrite!(&mut board, "{}", game).unwrap();
+
+    queue!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(ClearType::All)
+    )?;
+    // Raw mode does not translate newlines
+    for line in board.lines() {
+        write!(stdout, "{line}\r\n")?;
+    }
+    write!(stdout, "\r\nSeed: {seed:#x} ")?;
+    if let Some(level) = game.level() {
+        write!(stdout, "Level: {} ", level.index() + 1)?;
+    }
+    write!(
+        stdout,
+        "Score: {} Best: {} ",
+        game.score().apples,
+        game.best_score().apples
+    )?;
+    write!(stdout, "{status}\r\n")?;
+    write!(stdout, "{help}\r\n")?;
+    stdout.flush()
+}